@@ -0,0 +1,30 @@
+use std::path::PathBuf;
+use std::{env, fs};
+
+const SNAPSHOT_ENV: &str = "ANIME_GAME_DATA_SNAPSHOT";
+
+fn main() {
+    println!("cargo::rerun-if-changed=build.rs");
+    println!("cargo::rerun-if-env-changed={SNAPSHOT_ENV}");
+
+    if env::var_os("CARGO_FEATURE_EMBEDDED_SNAPSHOT").is_none() {
+        return;
+    }
+
+    let out_path = PathBuf::from(env::var_os("OUT_DIR").unwrap()).join("snapshot.json");
+    match env::var_os(SNAPSHOT_ENV) {
+        Some(snapshot_path) => {
+            let snapshot_path = PathBuf::from(snapshot_path);
+            println!("cargo::rerun-if-changed={}", snapshot_path.display());
+            fs::copy(&snapshot_path, &out_path).unwrap_or_else(|e| {
+                panic!("Unable to copy snapshot {}: {e}", snapshot_path.display())
+            });
+        }
+        None => {
+            // Still build without a snapshot so `--all-features` works, but
+            // make it obvious that nothing was embedded.
+            println!("cargo::warning={SNAPSHOT_ENV} is not set, no snapshot will be embedded");
+            fs::write(&out_path, "null").unwrap();
+        }
+    }
+}
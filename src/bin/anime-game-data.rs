@@ -1,9 +1,29 @@
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::PathBuf;
+
 use anime_game_data::AnimeGameData;
+use clap::Parser;
+
+#[derive(Parser)]
+struct Args {
+    /// Save the updated database to this path instead of printing it.  The
+    /// saved file can be embedded with the `embedded-snapshot` feature.
+    #[arg(long)]
+    save: Option<PathBuf>,
+}
 
 #[tokio::main]
 async fn main() {
+    let args = Args::parse();
     let mut data = AnimeGameData::new();
 
     data.update().await.unwrap();
-    println!("{:#?}", data);
+    match args.save {
+        Some(path) => {
+            let writer = BufWriter::new(File::create(path).unwrap());
+            data.save_to_writer(writer).unwrap();
+        }
+        None => println!("{:#?}", data),
+    }
 }
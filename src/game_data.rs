@@ -5,13 +5,76 @@ use serde::Deserialize;
 // entirely.  All fields are marked `#[serde(default)]` so entries missing
 // them still deserialize, with optional id fields typed as `Option`.
 
+// Unused stat slots hold FIGHT_PROP_NONE and are left as empty objects.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddPropEntry {
+    #[serde(default)]
+    pub prop_type: Option<String>,
+    #[serde(default)]
+    pub value: f64,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AvatarExcelConfigDataEntry {
+    #[serde(default)]
+    pub attack_base: f64,
+    #[serde(default)]
+    pub avatar_promote_id: u32,
+    #[serde(default)]
+    pub body_type: String,
+    #[serde(default)]
+    pub cand_skill_depot_ids: Vec<u32>,
+    #[serde(default)]
+    pub defense_base: f64,
+    #[serde(default)]
+    pub hp_base: f64,
+    #[serde(default)]
+    pub icon_name: String,
     #[serde(default)]
     pub id: u32,
     #[serde(default)]
+    pub initial_weapon: u32,
+    #[serde(default)]
     pub name_text_map_hash: u32,
+    #[serde(default)]
+    pub prop_grow_curves: Vec<PropGrowCurveEntry>,
+    #[serde(default)]
+    pub quality_type: String,
+    #[serde(default)]
+    pub side_icon_name: String,
+    #[serde(default)]
+    pub skill_depot_id: u32,
+    #[serde(default)]
+    pub weapon_type: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AvatarLevelExcelConfigDataEntry {
+    // The EXP needed to reach the next level.
+    #[serde(default)]
+    pub exp: u32,
+    #[serde(default)]
+    pub level: u32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AvatarPromoteExcelConfigDataEntry {
+    #[serde(default)]
+    pub add_props: Vec<AddPropEntry>,
+    #[serde(default)]
+    pub avatar_promote_id: u32,
+    #[serde(default)]
+    pub cost_items: Vec<ItemCountEntry>,
+    #[serde(default)]
+    pub promote_level: u32,
+    #[serde(default)]
+    pub scoin_cost: u32,
+    #[serde(default)]
+    pub unlock_max_level: u32,
 }
 
 #[derive(Debug, Deserialize)]
@@ -20,7 +83,13 @@ pub struct AvatarSkillDepotExcelConfigDataEntry {
     #[serde(default)]
     pub energy_skill: Option<u32>,
     #[serde(default)]
+    pub id: u32,
+    #[serde(default)]
+    pub inherent_proud_skill_opens: Vec<InherentProudSkillOpenEntry>,
+    #[serde(default)]
     skills: Vec<u32>,
+    #[serde(default)]
+    pub talents: Vec<u32>,
 }
 
 impl AvatarSkillDepotExcelConfigDataEntry {
@@ -28,6 +97,15 @@ impl AvatarSkillDepotExcelConfigDataEntry {
     pub fn skill(&self, index: usize) -> Option<u32> {
         self.skills.get(index).copied().filter(|&id| id != 0)
     }
+
+    // Every skill in the depot, including alternate sprints and the burst.
+    pub fn all_skills(&self) -> impl Iterator<Item = u32> + '_ {
+        self.skills
+            .iter()
+            .copied()
+            .filter(|&id| id != 0)
+            .chain(self.energy_skill)
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -37,6 +115,40 @@ pub struct AvatarSkillExcelConfigDataEntry {
     pub cost_elem_type: Option<String>,
     #[serde(default)]
     pub id: u32,
+    #[serde(default)]
+    pub name_text_map_hash: u32,
+    #[serde(default)]
+    pub proud_skill_group_id: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AvatarTalentExcelConfigDataEntry {
+    #[serde(default)]
+    pub desc_text_map_hash: u32,
+    #[serde(default)]
+    pub icon: String,
+    #[serde(default)]
+    pub name_text_map_hash: u32,
+    #[serde(default)]
+    pub talent_id: u32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CombineExcelConfigDataEntry {
+    #[serde(default)]
+    pub combine_id: u32,
+    #[serde(default)]
+    pub material_items: Vec<ItemCountEntry>,
+    #[serde(default)]
+    pub recipe_type: String,
+    #[serde(default)]
+    pub result_item_count: u32,
+    #[serde(default)]
+    pub result_item_id: u32,
+    #[serde(default)]
+    pub scoin_cost: u32,
 }
 
 #[derive(Debug, Deserialize)]
@@ -48,27 +160,282 @@ pub struct ConstValueExcelConfigDataEntry {
     pub value: Vec<String>,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CookRecipeExcelConfigDataEntry {
+    #[serde(default)]
+    pub id: u32,
+    #[serde(default)]
+    pub input_vec: Vec<ItemCountEntry>,
+    #[serde(default)]
+    pub quality_output_vec: Vec<ItemCountEntry>,
+}
+
+// Shared by the avatar, weapon, and monster curve tables.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurveExcelConfigDataEntry {
+    #[serde(default)]
+    pub curve_infos: Vec<CurveInfoEntry>,
+    #[serde(default)]
+    pub level: u32,
+}
+
+// Every curve multiplies its base value, so `arith` is not indexed.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurveInfoEntry {
+    #[serde(default, rename = "type")]
+    pub curve_type: String,
+    #[serde(default)]
+    pub value: f64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyDungeonConfigDataEntry {
+    #[serde(default)]
+    pub friday: Vec<u32>,
+    #[serde(default)]
+    pub monday: Vec<u32>,
+    #[serde(default)]
+    pub saturday: Vec<u32>,
+    #[serde(default)]
+    pub sunday: Vec<u32>,
+    #[serde(default)]
+    pub thursday: Vec<u32>,
+    #[serde(default)]
+    pub tuesday: Vec<u32>,
+    #[serde(default)]
+    pub wednesday: Vec<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DungeonEntryExcelConfigDataEntry {
+    #[serde(default)]
+    pub cooldown_tips_dungeon_id: Vec<u32>,
+    #[serde(default)]
+    pub desc_text_map_hash: u32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DungeonExcelConfigDataEntry {
+    #[serde(default)]
+    pub id: u32,
+    #[serde(default)]
+    pub limit_level: u32,
+    #[serde(default)]
+    pub name_text_map_hash: u32,
+    #[serde(default, rename = "passRewardPreviewID")]
+    pub pass_reward_preview_id: Option<u32>,
+    #[serde(default)]
+    pub show_level: u32,
+    #[serde(default)]
+    pub sub_type: String,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct EquipAffixExcelConfigDataEntry {
+    #[serde(default)]
+    pub add_props: Vec<AddPropEntry>,
+    #[serde(default)]
+    pub desc_text_map_hash: u32,
     #[serde(default)]
     pub id: u32,
     #[serde(default)]
+    pub level: u32,
+    #[serde(default)]
     pub name_text_map_hash: u32,
+    #[serde(default)]
+    pub param_list: Vec<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FetterInfoExcelConfigDataEntry {
+    // Sic, the game data spells "before" without the trailing "e".
+    #[serde(default, rename = "avatarConstellationBeforTextMapHash")]
+    pub avatar_constellation_text_map_hash: u32,
+    #[serde(default)]
+    pub avatar_detail_text_map_hash: u32,
+    #[serde(default)]
+    pub avatar_id: u32,
+    #[serde(default)]
+    pub avatar_native_text_map_hash: u32,
+    #[serde(default)]
+    pub avatar_title_text_map_hash: u32,
+    #[serde(default, rename = "avatarVisionBeforTextMapHash")]
+    pub avatar_vision_text_map_hash: u32,
+    #[serde(default)]
+    pub cv_chinese_text_map_hash: u32,
+    #[serde(default)]
+    pub cv_english_text_map_hash: u32,
+    #[serde(default)]
+    pub cv_japanese_text_map_hash: u32,
+    #[serde(default)]
+    pub cv_korean_text_map_hash: u32,
+    #[serde(default)]
+    pub info_birth_day: u32,
+    #[serde(default)]
+    pub info_birth_month: u32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForgeExcelConfigDataEntry {
+    #[serde(default)]
+    pub id: u32,
+    #[serde(default)]
+    pub material_items: Vec<ItemCountEntry>,
+    #[serde(default)]
+    pub result_item_count: u32,
+    #[serde(default)]
+    pub result_item_id: u32,
+    #[serde(default)]
+    pub scoin_cost: u32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InherentProudSkillOpenEntry {
+    #[serde(default)]
+    pub need_avatar_promote_level: u32,
+    #[serde(default)]
+    pub proud_skill_group_id: u32,
+}
+
+// Unused cost slots are empty objects.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemCountEntry {
+    #[serde(default)]
+    pub count: u32,
+    #[serde(default)]
+    pub id: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemUseEntry {
+    #[serde(default)]
+    pub use_op: String,
+    #[serde(default)]
+    pub use_param: Vec<String>,
 }
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MaterialExcelConfigDataEntry {
+    #[serde(default)]
+    pub desc_text_map_hash: u32,
+    #[serde(default)]
+    pub icon: String,
+    #[serde(default)]
+    pub id: u32,
+    #[serde(default)]
+    pub item_type: String,
+    #[serde(default)]
+    pub item_use: Vec<ItemUseEntry>,
+    #[serde(default)]
+    pub material_type: Option<String>,
+    #[serde(default)]
+    pub name_text_map_hash: u32,
+    #[serde(default)]
+    pub rank_level: u32,
+    #[serde(default)]
+    pub stack_limit: u32,
+    #[serde(default)]
+    pub type_desc_text_map_hash: u32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonsterDescribeExcelConfigDataEntry {
+    #[serde(default)]
+    pub icon: String,
+    #[serde(default)]
+    pub id: u32,
+    #[serde(default)]
+    pub name_text_map_hash: u32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonsterExcelConfigDataEntry {
+    #[serde(default)]
+    pub attack_base: f64,
+    #[serde(default)]
+    pub defense_base: f64,
+    #[serde(default)]
+    pub describe_id: Option<u32>,
+    #[serde(default)]
+    pub elec_sub_hurt: f64,
+    #[serde(default)]
+    pub fire_sub_hurt: f64,
+    #[serde(default)]
+    pub grass_sub_hurt: f64,
+    #[serde(default)]
+    pub hp_base: f64,
+    #[serde(default)]
+    pub ice_sub_hurt: f64,
     #[serde(default)]
     pub id: u32,
     #[serde(default)]
     pub name_text_map_hash: u32,
+    #[serde(default)]
+    pub physical_sub_hurt: f64,
+    #[serde(default)]
+    pub prop_grow_curves: Vec<PropGrowCurveEntry>,
+    #[serde(default)]
+    pub rock_sub_hurt: f64,
+    #[serde(default)]
+    pub water_sub_hurt: f64,
+    #[serde(default)]
+    pub wind_sub_hurt: f64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PropGrowCurveEntry {
+    #[serde(default)]
+    pub grow_curve: String,
+    #[serde(default, rename = "type")]
+    pub prop_type: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProudSkillExcelConfigDataEntry {
+    #[serde(default)]
+    pub add_props: Vec<AddPropEntry>,
+    #[serde(default)]
+    pub coin_cost: u32,
+    #[serde(default)]
+    pub cost_items: Vec<ItemCountEntry>,
+    #[serde(default)]
+    pub desc_text_map_hash: u32,
+    #[serde(default)]
+    pub icon: String,
+    #[serde(default)]
+    pub level: u32,
+    #[serde(default)]
+    pub name_text_map_hash: u32,
+    #[serde(default)]
+    pub param_desc_list: Vec<u32>,
+    #[serde(default)]
+    pub param_list: Vec<f64>,
+    #[serde(default)]
+    pub proud_skill_group_id: u32,
 }
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ReliquaryAffixExcelConfigDataEntry {
+    #[serde(default)]
+    pub depot_id: u32,
     #[serde(default)]
     pub id: u32,
     #[serde(default)]
@@ -80,23 +447,43 @@ pub struct ReliquaryAffixExcelConfigDataEntry {
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ReliquaryExcelConfigDataEntry {
+    #[serde(default)]
+    pub append_prop_depot_id: u32,
     #[serde(default)]
     pub equip_type: String,
     #[serde(default)]
     pub id: u32,
     #[serde(default)]
+    pub main_prop_depot_id: u32,
+    #[serde(default)]
     pub rank_level: u32,
     #[serde(default)]
     pub set_id: Option<u32>,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReliquaryLevelExcelConfigDataEntry {
+    #[serde(default)]
+    pub add_props: Vec<AddPropEntry>,
+    #[serde(default)]
+    pub level: u32,
+    // Omitted by the rows that only hold shared EXP requirements.
+    #[serde(default)]
+    pub rank: Option<u32>,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ReliquaryMainPropExcelConfigDataEntry {
     #[serde(default)]
     pub id: u32,
     #[serde(default)]
+    pub prop_depot_id: u32,
+    #[serde(default)]
     pub prop_type: String,
+    #[serde(default)]
+    pub weight: Option<u32>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -106,15 +493,87 @@ pub struct ReliquarySetExcelConfigDataEntry {
     pub equip_affix_id: Option<u32>,
     #[serde(default)]
     pub set_id: u32,
+    #[serde(default)]
+    pub set_need_num: Vec<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RewardPreviewExcelConfigDataEntry {
+    #[serde(default)]
+    pub id: u32,
+    #[serde(default)]
+    pub preview_items: Vec<RewardPreviewItemEntry>,
+}
+
+// Counts are free-form strings like "1;3", so only the ids are kept.  Unused
+// slots are left as empty objects.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RewardPreviewItemEntry {
+    #[serde(default)]
+    pub id: Option<u32>,
 }
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct WeaponExcelConfigDataEntry {
+    #[serde(default)]
+    pub awaken_costs: Vec<u32>,
+    #[serde(default)]
+    pub awaken_material: u32,
     #[serde(default)]
     pub id: u32,
     #[serde(default)]
     pub name_text_map_hash: u32,
     #[serde(default)]
     pub rank_level: u32,
+    #[serde(default)]
+    pub skill_affix: Vec<u32>,
+    #[serde(default)]
+    pub weapon_base_exp: u32,
+    #[serde(default)]
+    pub weapon_promote_id: u32,
+    #[serde(default)]
+    pub weapon_prop: Vec<WeaponPropEntry>,
+    #[serde(default)]
+    pub weapon_type: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WeaponLevelExcelConfigDataEntry {
+    #[serde(default)]
+    pub level: u32,
+    // Indexed by rarity - 1.
+    #[serde(default)]
+    pub required_exps: Vec<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WeaponPromoteExcelConfigDataEntry {
+    #[serde(default)]
+    pub add_props: Vec<AddPropEntry>,
+    #[serde(default)]
+    pub coin_cost: u32,
+    #[serde(default)]
+    pub cost_items: Vec<ItemCountEntry>,
+    #[serde(default)]
+    pub promote_level: u32,
+    #[serde(default)]
+    pub unlock_max_level: u32,
+    #[serde(default)]
+    pub weapon_promote_id: u32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WeaponPropEntry {
+    #[serde(default)]
+    pub init_value: f64,
+    #[serde(default)]
+    pub prop_type: Option<String>,
+    #[serde(default, rename = "type")]
+    pub curve_type: String,
 }
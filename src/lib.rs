@@ -1,24 +1,43 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
 use std::fs::File;
-use std::io::{BufReader, BufWriter, Read, Write};
+use std::io::{BufReader, BufWriter, ErrorKind, Read, Write};
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::sync::Arc;
+#[cfg(feature = "embedded-snapshot")]
+use std::sync::OnceLock;
 
 use anyhow::{Result, anyhow};
 
 mod dimbreath;
 mod game_data;
+#[cfg(feature = "shared")]
+mod shared;
+#[cfg(feature = "sqlite")]
+mod sqlite;
 mod types;
 
 use dimbreath::Dimbreath;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "shared")]
+pub use shared::SharedAnimeGameData;
 pub use types::*;
 
 use crate::game_data::{
-    AvatarExcelConfigDataEntry, AvatarSkillExcelConfigDataEntry, ConstValueExcelConfigDataEntry,
-    MaterialExcelConfigDataEntry, ReliquaryMainPropExcelConfigDataEntry,
-    WeaponExcelConfigDataEntry,
+    AddPropEntry, AvatarExcelConfigDataEntry, AvatarLevelExcelConfigDataEntry,
+    AvatarPromoteExcelConfigDataEntry, AvatarSkillDepotExcelConfigDataEntry,
+    AvatarSkillExcelConfigDataEntry, AvatarTalentExcelConfigDataEntry, CombineExcelConfigDataEntry,
+    ConstValueExcelConfigDataEntry, CookRecipeExcelConfigDataEntry, CurveExcelConfigDataEntry,
+    DailyDungeonConfigDataEntry, DungeonEntryExcelConfigDataEntry, DungeonExcelConfigDataEntry,
+    EquipAffixExcelConfigDataEntry, FetterInfoExcelConfigDataEntry, ForgeExcelConfigDataEntry,
+    ItemCountEntry, MaterialExcelConfigDataEntry, MonsterDescribeExcelConfigDataEntry,
+    MonsterExcelConfigDataEntry, ProudSkillExcelConfigDataEntry,
+    ReliquaryAffixExcelConfigDataEntry, ReliquaryExcelConfigDataEntry,
+    ReliquaryLevelExcelConfigDataEntry, ReliquaryMainPropExcelConfigDataEntry,
+    RewardPreviewExcelConfigDataEntry, WeaponExcelConfigDataEntry, WeaponLevelExcelConfigDataEntry,
+    WeaponPromoteExcelConfigDataEntry,
 };
 
 trait GameDataSource {
@@ -50,23 +69,386 @@ fn lookup_const_value<T: FromStr>(
     res
 }
 
-const DATABASE_VERSION: u32 = 4;
+// Percentage values are stored as fractions, but are indexed multiplied by 100
+// to match how they are displayed.
+fn make_affix(property: Property, value: f64) -> Affix {
+    let value = if property.is_percentage() {
+        value * 100.
+    } else {
+        value
+    };
+    Affix { property, value }
+}
+
+fn stats_from_add_props(add_props: &[AddPropEntry]) -> Vec<Affix> {
+    add_props
+        .iter()
+        .filter_map(|prop| {
+            let property = prop.prop_type.as_ref()?.parse::<Property>().ok()?;
+            Some(make_affix(property, prop.value))
+        })
+        .collect()
+}
+
+fn make_cost(mora: u32, items: &[ItemCountEntry]) -> Cost {
+    let mut cost = Cost {
+        mora,
+        ..Default::default()
+    };
+    for item in items {
+        if let Some(id) = item.id {
+            *cost.materials.entry(id).or_default() += item.count;
+        }
+    }
+    cost
+}
+
+// Expands an item into the materials it is crafted from, recursively.  Crafts
+// are rounded up, so leftovers of intermediate tiers are not reused.  Items
+// already being expanded are kept as is to guard against cycles.
+fn expand_crafting(
+    crafting: &BTreeMap<u32, Recipe>,
+    crafting_output_map: &BTreeMap<u32, u32>,
+    id: u32,
+    count: u32,
+    cost: &mut Cost,
+    path: &mut Vec<u32>,
+) -> Result<()> {
+    let overflow = || anyhow!("Unable to expand {count} of item {id} without overflowing");
+    let recipe = crafting_output_map
+        .get(&id)
+        .and_then(|recipe_id| crafting.get(recipe_id));
+    let Some(recipe) = recipe.filter(|_| !path.contains(&id)) else {
+        let total = cost.materials.entry(id).or_default();
+        *total = total.checked_add(count).ok_or_else(overflow)?;
+        return Ok(());
+    };
+    let crafts = count.div_ceil(recipe.outputs[0].count);
+    cost.mora = crafts
+        .checked_mul(recipe.mora)
+        .and_then(|mora| cost.mora.checked_add(mora))
+        .ok_or_else(overflow)?;
+    path.push(id);
+    for input in &recipe.inputs {
+        let input_count = input.count.checked_mul(crafts).ok_or_else(overflow)?;
+        expand_crafting(
+            crafting,
+            crafting_output_map,
+            input.id,
+            input_count,
+            cost,
+            path,
+        )?;
+    }
+    path.pop();
+    Ok(())
+}
+
+// Items granting EXP through `op`, with the EXP each grants, largest first.
+fn exp_items(material_map: &BTreeMap<u32, Material>, op: &str) -> Vec<(u32, u32)> {
+    let mut items: Vec<(u32, u32)> = material_map
+        .iter()
+        .flat_map(|(id, material)| {
+            material
+                .item_use
+                .iter()
+                .filter(|item_use| item_use.op == op)
+                .filter_map(|item_use| Some((*id, item_use.params.first()?.parse().ok()?)))
+        })
+        .filter(|(_, exp)| *exp > 0)
+        .collect();
+    items.sort_by_key(|(_, exp)| std::cmp::Reverse(*exp));
+    items
+}
+
+// Splits a level range at the ascension caps, which must be ascending.  EXP
+// fed past a cap is lost, so each segment is fed separately.
+fn level_segments(caps: impl IntoIterator<Item = u32>, from: u32, to: u32) -> Vec<(u32, u32)> {
+    let mut segments = Vec::new();
+    let mut start = from;
+    for cap in caps {
+        if cap > start && cap < to {
+            segments.push((start, cap));
+            start = cap;
+        }
+    }
+    segments.push((start, to));
+    segments
+}
+
+// Character EXP costs 1 Mora per 5 EXP, e.g. 4000 Mora per Hero's Wit.
+const CHARACTER_EXP_MORA_RATE: u32 = 5;
+
+// Weapon EXP costs 1 Mora per 10 EXP, e.g. 1000 Mora per Mystic Enhancement
+// Ore.
+const WEAPON_EXP_MORA_RATE: u32 = 10;
+
+// Entry N of `exps` holds the EXP to level from N + 1 to N + 2.  Each segment
+// is fed items largest first, topping up with the smallest one, and Mora is
+// charged for all EXP fed, including any overshoot.
+fn level_up_cost(
+    exps: &[u32],
+    items: &[(u32, u32)],
+    segments: &[(u32, u32)],
+    exp_per_mora: u32,
+) -> Result<LevelUpCost> {
+    let mut cost = Cost::default();
+    let mut exp = 0;
+    let mut fed = 0;
+    for (from, to) in segments {
+        let mut remaining: u32 = exps[*from as usize - 1..*to as usize - 1].iter().sum();
+        exp += remaining;
+        for (item, item_exp) in items {
+            let count = remaining / item_exp;
+            if count > 0 {
+                *cost.materials.entry(*item).or_default() += count;
+                remaining -= count * item_exp;
+                fed += count * item_exp;
+            }
+        }
+        if remaining > 0 {
+            let (item, item_exp) = items
+                .last()
+                .ok_or_else(|| anyhow!("Unable to find EXP items"))?;
+            *cost.materials.entry(*item).or_default() += 1;
+            fed += item_exp;
+        }
+    }
+    cost.mora = fed / exp_per_mora;
+    Ok(LevelUpCost { exp, cost })
+}
+
+// Each phase holds the cost of ascending to it from the previous one.
+fn sum_ascension_cost(
+    ascension_map: &BTreeMap<u32, Vec<Ascension>>,
+    promote_id: u32,
+    from: u32,
+    to: u32,
+) -> Result<Cost> {
+    let phases = ascension_map
+        .get(&promote_id)
+        .ok_or_else(|| anyhow!("Unable to fetch ascensions {promote_id}"))?;
+    if from > to || to as usize >= phases.len() {
+        return Err(anyhow!("Unable to ascend {promote_id} from {from} to {to}"));
+    }
+    let mut cost = Cost::default();
+    for phase in &phases[from as usize + 1..=to as usize] {
+        cost.add(&phase.cost);
+    }
+    Ok(cost)
+}
+
+fn lookup_curve(
+    curve_map: &BTreeMap<String, BTreeMap<u32, f64>>,
+    curve: &str,
+    level: u32,
+) -> Result<f64> {
+    curve_map
+        .get(curve)
+        .and_then(|levels| levels.get(&level))
+        .copied()
+        .ok_or_else(|| anyhow!("Unable to fetch curve {curve} at level {level}"))
+}
+
+// Each ascension phase covers the levels from the previous phase's max level
+// up to its own.
+fn lookup_ascension(
+    ascension_map: &BTreeMap<u32, Vec<Ascension>>,
+    promote_id: u32,
+    level: u32,
+    ascension: u32,
+) -> Result<&Ascension> {
+    let phases = ascension_map
+        .get(&promote_id)
+        .ok_or_else(|| anyhow!("Unable to fetch ascensions {promote_id}"))?;
+    let phase = phases
+        .get(ascension as usize)
+        .ok_or_else(|| anyhow!("Unable to fetch ascension {ascension} of {promote_id}"))?;
+    let min_level = match ascension {
+        0 => 1,
+        _ => phases[ascension as usize - 1].max_level,
+    };
+    if level < min_level || level > phase.max_level {
+        return Err(anyhow!(
+            "Level {level} is not reachable at ascension {ascension}"
+        ));
+    }
+    Ok(phase)
+}
+
+// A 5 star artifact starts with up to 4 substats and gets 5 more rolls.
+const MAX_SUBSTAT_ROLLS: usize = 6;
+
+// Finds the fewest rolls whose total matches `value` once rounded for
+// display, preferring the closest total among them.  Returns `None` if no
+// combination of up to `MAX_SUBSTAT_ROLLS` rolls matches.
+fn decompose_rolls(tiers: &[f64], value: f64, tolerance: f64) -> Option<Vec<f64>> {
+    fn search(
+        tiers: &[f64],
+        remaining: usize,
+        rolls: &mut Vec<f64>,
+        value: f64,
+        best: &mut Option<(f64, Vec<f64>)>,
+    ) {
+        if remaining == 0 {
+            let error = (rolls.iter().sum::<f64>() - value).abs();
+            if best
+                .as_ref()
+                .is_none_or(|(best_error, _)| error < *best_error)
+            {
+                *best = Some((error, rolls.clone()));
+            }
+            return;
+        }
+        // Rolls are unordered, so only visit non-decreasing tier sequences.
+        for (i, tier) in tiers.iter().enumerate() {
+            rolls.push(*tier);
+            search(&tiers[i..], remaining - 1, rolls, value, best);
+            rolls.pop();
+        }
+    }
+
+    for count in 1..=MAX_SUBSTAT_ROLLS {
+        let mut best = None;
+        search(tiers, count, &mut Vec::new(), value, &mut best);
+        if let Some((error, rolls)) = best
+            && error <= tolerance
+        {
+            return Some(rolls);
+        }
+    }
+    None
+}
+
+// Skill level constellations aren't linked to their skill in the data.  They
+// use the "U" icon variant and name the skill in their description, and
+// always grant 3 levels.
+fn skill_level_bonus(
+    icon: &str,
+    description: &str,
+    skill_names: &[(u32, &String)],
+) -> Option<SkillLevelBonus> {
+    if !icon.starts_with("UI_Talent_U_") {
+        return None;
+    }
+    let (skill_id, _) = skill_names
+        .iter()
+        .filter_map(|(id, name)| Some((*id, description.find(name.as_str())?)))
+        .min_by_key(|(_, position)| *position)?;
+    Some(SkillLevelBonus {
+        skill_id,
+        levels: 3,
+    })
+}
+
+fn rarity_from_quality_type(quality_type: &str) -> Option<u32> {
+    match quality_type {
+        // Aloy's quality is special cased but she is still a 5 star.
+        "QUALITY_ORANGE" | "QUALITY_ORANGE_SP" => Some(5),
+        "QUALITY_PURPLE" => Some(4),
+        _ => None,
+    }
+}
+
+const DATABASE_VERSION: u32 = 30;
+
+// Stats added on top of the curve scaled base stats once ascended to a phase.
+#[derive(Debug, Deserialize, Serialize)]
+struct Ascension {
+    max_level: u32,
+    hp: f64,
+    attack: f64,
+    defense: f64,
+    bonus: Option<Affix>,
+    cost: Cost,
+}
+
+impl Ascension {
+    fn from_add_props(max_level: u32, add_props: &[AddPropEntry]) -> Self {
+        let mut ascension = Self {
+            max_level,
+            hp: 0.,
+            attack: 0.,
+            defense: 0.,
+            bonus: None,
+            cost: Cost::default(),
+        };
+        for prop in add_props {
+            let Some(prop_type) = &prop.prop_type else {
+                continue;
+            };
+            match prop_type.as_str() {
+                "FIGHT_PROP_BASE_HP" => ascension.hp = prop.value,
+                "FIGHT_PROP_BASE_ATTACK" => ascension.attack = prop.value,
+                "FIGHT_PROP_BASE_DEFENSE" => ascension.defense = prop.value,
+                _ => {
+                    if let Ok(property) = prop_type.parse::<Property>() {
+                        ascension.bonus = Some(make_affix(property, prop.value));
+                    }
+                }
+            }
+        }
+        ascension
+    }
+}
+
+// Written by build.rs from the file named by `ANIME_GAME_DATA_SNAPSHOT`, or
+// `null` if no snapshot was provided.
+#[cfg(feature = "embedded-snapshot")]
+const EMBEDDED_SNAPSHOT: &str = include_str!(concat!(env!("OUT_DIR"), "/snapshot.json"));
 
+// Maps are ordered by id so that saving the same data always produces the
+// same bytes.
 #[derive(Debug, Deserialize, Serialize)]
 struct Database {
     version: u32,
     git_hash: String,
-    affix_map: HashMap<u32, Affix>,
-    artifact_map: HashMap<u32, Artifact>,
-    character_map: HashMap<u32, String>,
-    material_map: HashMap<u32, String>,
-    property_map: HashMap<u32, Property>,
-    set_map: HashMap<u32, String>,
-    skill_element_map: HashMap<u32, Element>,
-    skill_type_map: HashMap<u32, SkillType>,
+    affix_map: BTreeMap<u32, Affix>,
+    artifact_map: BTreeMap<u32, Artifact>,
+    character_ascension_map: BTreeMap<u32, Vec<Ascension>>,
+    character_curve_map: BTreeMap<String, BTreeMap<u32, f64>>,
+    // Entry N holds the EXP to level from N + 1 to N + 2.
+    character_level_exps: Vec<u32>,
+    character_map: BTreeMap<u32, Character>,
+    character_profile_map: BTreeMap<u32, CharacterProfile>,
+    // Keyed by skill depot, since that is what links a character to them.
+    constellation_map: BTreeMap<u32, Vec<Constellation>>,
+    // Item to the crafting recipe making it, for recipes with a single output.
+    crafting_output_map: BTreeMap<u32, u32>,
+    // Skill depot to the characters using it.
+    depot_owner_map: BTreeMap<u32, Vec<u32>>,
+    // Skill depot to its Auto, Skill and Burst, skipping missing ones.
+    depot_skill_map: BTreeMap<u32, Vec<u32>>,
+    domain_map: BTreeMap<u32, Domain>,
+    // Keyed by rarity, then main stat, then level starting at 0.
+    main_stat_map: BTreeMap<u32, BTreeMap<Property, BTreeMap<u32, f64>>>,
+    main_stat_option_map: BTreeMap<ArtifactSlot, BTreeMap<u32, Vec<MainStatOption>>>,
+    material_map: BTreeMap<u32, Material>,
+    monster_curve_map: BTreeMap<String, BTreeMap<u32, f64>>,
+    monster_map: BTreeMap<u32, Monster>,
+    // Keyed by skill depot, like constellations.
+    passive_map: BTreeMap<u32, Vec<Passive>>,
+    property_map: BTreeMap<u32, Property>,
+    recipe_map: BTreeMap<RecipeKind, BTreeMap<u32, Recipe>>,
+    set_map: BTreeMap<u32, ArtifactSet>,
+    skill_element_map: BTreeMap<u32, Element>,
+    // Skill to the skill depots containing it.
+    skill_owner_map: BTreeMap<u32, Vec<u32>>,
+    skill_scaling_map: BTreeMap<u32, SkillScaling>,
+    skill_type_map: BTreeMap<u32, SkillType>,
+    // Keyed by rarity, then substat.  Roll tiers are sorted ascending.
+    substat_map: BTreeMap<u32, BTreeMap<Property, Vec<f64>>>,
+    // Entry N holds the cost of levelling a skill from N + 1 to N + 2.
+    talent_cost_map: BTreeMap<u32, Vec<Cost>>,
     tps_avatar_id_female: Option<u32>,
     tps_avatar_id_male: Option<u32>,
-    weapon_map: HashMap<u32, Weapon>,
+    traveler: Option<Traveler>,
+    weapon_ascension_map: BTreeMap<u32, Vec<Ascension>>,
+    weapon_curve_map: BTreeMap<String, BTreeMap<u32, f64>>,
+    // Keyed by rarity.  Entry N holds the EXP to level from N + 1 to N + 2.
+    weapon_level_exp_map: BTreeMap<u32, Vec<u32>>,
+    weapon_map: BTreeMap<u32, Weapon>,
+    weapon_passive_map: BTreeMap<u32, WeaponPassive>,
 }
 
 impl Database {
@@ -74,68 +456,232 @@ impl Database {
         Self {
             version: DATABASE_VERSION,
             git_hash: git_hash.into(),
-            affix_map: HashMap::new(),
-            artifact_map: HashMap::new(),
-            character_map: HashMap::new(),
-            material_map: HashMap::new(),
-            property_map: HashMap::new(),
-            set_map: HashMap::new(),
-            skill_element_map: HashMap::new(),
-            skill_type_map: HashMap::new(),
+            affix_map: BTreeMap::new(),
+            artifact_map: BTreeMap::new(),
+            character_ascension_map: BTreeMap::new(),
+            character_curve_map: BTreeMap::new(),
+            character_level_exps: Vec::new(),
+            character_map: BTreeMap::new(),
+            character_profile_map: BTreeMap::new(),
+            constellation_map: BTreeMap::new(),
+            crafting_output_map: BTreeMap::new(),
+            depot_owner_map: BTreeMap::new(),
+            depot_skill_map: BTreeMap::new(),
+            domain_map: BTreeMap::new(),
+            main_stat_map: BTreeMap::new(),
+            main_stat_option_map: BTreeMap::new(),
+            material_map: BTreeMap::new(),
+            monster_curve_map: BTreeMap::new(),
+            monster_map: BTreeMap::new(),
+            passive_map: BTreeMap::new(),
+            property_map: BTreeMap::new(),
+            recipe_map: BTreeMap::new(),
+            set_map: BTreeMap::new(),
+            skill_element_map: BTreeMap::new(),
+            skill_owner_map: BTreeMap::new(),
+            skill_scaling_map: BTreeMap::new(),
+            skill_type_map: BTreeMap::new(),
+            substat_map: BTreeMap::new(),
+            talent_cost_map: BTreeMap::new(),
             tps_avatar_id_female: None,
             tps_avatar_id_male: None,
-            weapon_map: HashMap::new(),
+            traveler: None,
+            weapon_ascension_map: BTreeMap::new(),
+            weapon_curve_map: BTreeMap::new(),
+            weapon_level_exp_map: BTreeMap::new(),
+            weapon_map: BTreeMap::new(),
+            weapon_passive_map: BTreeMap::new(),
         }
     }
 
-    pub fn load_from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
-        let path = path.as_ref();
-        let file = File::open(path)?;
-        let reader = BufReader::new(file);
-        let db: Self = serde_json::from_reader(reader)?;
-        if db.version != DATABASE_VERSION {
-            return Err(anyhow!(
-                "Database versions do not match {} != {}",
-                db.version,
-                DATABASE_VERSION
-            ));
+    pub fn load_from_path<P: AsRef<Path>>(path: P) -> Result<Self, LoadError> {
+        let file = File::open(path).map_err(|e| match e.kind() {
+            ErrorKind::NotFound => LoadError::Missing,
+            _ => LoadError::Unreadable(e.to_string()),
+        })?;
+        Self::load_from_reader(BufReader::new(file))
+    }
+
+    pub fn load_from_reader<R: Read>(mut reader: R) -> Result<Self, LoadError> {
+        let mut json = String::new();
+        reader
+            .read_to_string(&mut json)
+            .map_err(|e| LoadError::Unreadable(e.to_string()))?;
+
+        // Check the version before parsing the whole database so that older
+        // layouts are reported as such rather than as parse errors.
+        #[derive(Deserialize)]
+        struct Version {
+            version: u32,
         }
+        let Version { version } = serde_json::from_str(&json)?;
+        if version != DATABASE_VERSION {
+            return Err(LoadError::WrongVersion {
+                found: version,
+                expected: DATABASE_VERSION,
+            });
+        }
+
+        let db: Self = serde_json::from_str(&json)?;
+        db.check_integrity().map_err(LoadError::IntegrityMismatch)?;
         Ok(db)
     }
+
+    // Checks that references between maps resolve.
+    fn check_integrity(&self) -> Result<(), String> {
+        if self.git_hash.is_empty() {
+            return Err("missing git hash".into());
+        }
+        for (id, artifact) in &self.artifact_map {
+            if !self.set_map.contains_key(&artifact.set_id) {
+                return Err(format!("artifact {id} has unknown set {}", artifact.set_id));
+            }
+        }
+        Ok(())
+    }
+
+    // A `null` snapshot means none was embedded.
+    #[cfg(any(test, feature = "embedded-snapshot"))]
+    fn load_snapshot(json: &str) -> Option<Self> {
+        if json == "null" {
+            return None;
+        }
+        Self::load_from_reader(json.as_bytes())
+            .inspect_err(|e| tracing::warn!("Ignoring embedded snapshot: {e}"))
+            .ok()
+    }
+
+    // The snapshot is parsed once and shared by every instance using it.
+    #[cfg(feature = "embedded-snapshot")]
+    fn load_embedded() -> Option<Arc<Self>> {
+        static SNAPSHOT: OnceLock<Option<Arc<Database>>> = OnceLock::new();
+        SNAPSHOT
+            .get_or_init(|| Self::load_snapshot(EMBEDDED_SNAPSHOT).map(Arc::new))
+            .clone()
+    }
+
+    #[cfg(not(feature = "embedded-snapshot"))]
+    fn load_embedded() -> Option<Arc<Self>> {
+        None
+    }
+}
+
+// Why a cache or reader could not be loaded.
+#[derive(Clone, Debug, PartialEq)]
+pub enum LoadError {
+    Missing,
+    Unreadable(String),
+    WrongVersion {
+        found: u32,
+        expected: u32,
+    },
+    ParseError {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    IntegrityMismatch(String),
+}
+
+impl From<serde_json::Error> for LoadError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::ParseError {
+            line: e.line(),
+            column: e.column(),
+            message: e.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::Missing => write!(f, "No data found"),
+            LoadError::Unreadable(e) => write!(f, "Unable to read data: {e}"),
+            LoadError::WrongVersion { found, expected } => {
+                write!(f, "Database versions do not match {found} != {expected}")
+            }
+            LoadError::ParseError {
+                line,
+                column,
+                message,
+            } => write!(f, "Unable to parse data at {line}:{column}: {message}"),
+            LoadError::IntegrityMismatch(e) => write!(f, "Data failed integrity check: {e}"),
+        }
+    }
 }
 
-#[derive(Debug)]
+impl std::error::Error for LoadError {}
+
+// Where the current data came from.
+#[derive(Clone, Debug, PartialEq)]
+pub enum LoadOutcome {
+    // Loaded from the cache or reader.
+    Loaded,
+    // Downloaded by `update`, superseding whatever was loaded before.
+    Updated,
+    // No cache or reader data was used.  `reason` says why the cache was
+    // discarded, and is `None` if no cache was given.  `embedded` is set when
+    // the embedded snapshot was loaded in its place.
+    Fallback {
+        reason: Option<LoadError>,
+        embedded: bool,
+    },
+}
+
+// The database is never modified once indexed, so clones share it and stay
+// consistent even as other clones are updated.
+#[derive(Clone, Debug)]
 pub struct AnimeGameData {
     cache_path: Option<PathBuf>,
-    db: Option<Database>,
+    db: Option<Arc<Database>>,
+    load_outcome: LoadOutcome,
 }
 
 impl AnimeGameData {
     pub fn new() -> Self {
+        let db = Database::load_embedded();
         Self {
             cache_path: None,
-            db: None,
+            load_outcome: LoadOutcome::Fallback {
+                reason: None,
+                embedded: db.is_some(),
+            },
+            db,
         }
     }
 
     pub fn new_from_reader<R: Read>(reader: R) -> Result<Self> {
-        let db = serde_json::from_reader(reader)?;
+        let db = Database::load_from_reader(reader)?;
         Ok(Self {
             cache_path: None,
-            db,
+            db: Some(Arc::new(db)),
+            load_outcome: LoadOutcome::Loaded,
         })
     }
 
     pub fn new_with_cache<P: AsRef<Path>>(cache_path: P) -> Self {
         let cache_path = cache_path.as_ref();
 
-        // Try to load cached data ignoring errors and instead leave and empty
-        // database.
-        let db = Database::load_from_path(cache_path).ok();
+        // Try to load cached data, recording why it was discarded and instead
+        // falling back to the embedded snapshot, if any, or an empty database.
+        let (db, load_outcome) = match Database::load_from_path(cache_path) {
+            Ok(db) => (Some(Arc::new(db)), LoadOutcome::Loaded),
+            Err(e) => {
+                tracing::info!("Discarding cache {}: {e}", cache_path.display());
+                let db = Database::load_embedded();
+                let outcome = LoadOutcome::Fallback {
+                    reason: Some(e),
+                    embedded: db.is_some(),
+                };
+                (db, outcome)
+            }
+        };
 
         Self {
             cache_path: Some(cache_path.to_owned()),
             db,
+            load_outcome,
         }
     }
 
@@ -144,8 +690,30 @@ impl AnimeGameData {
         Ok(())
     }
 
+    // Describes where the current data came from, including why the cache was
+    // discarded if it was.
+    pub fn load_outcome(&self) -> &LoadOutcome {
+        &self.load_outcome
+    }
+
     fn db(&self) -> Result<&Database> {
-        self.db.as_ref().ok_or_else(|| anyhow!("No data loaded"))
+        self.db.as_deref().ok_or_else(|| anyhow!("No data loaded"))
+    }
+
+    // Splits a displayed substat total into its individual rolls.
+    pub fn decompose_substat(
+        &self,
+        rarity: u32,
+        property: Property,
+        value: f64,
+    ) -> Result<Vec<f64>> {
+        let tiers = self.get_substat_tiers(rarity, property)?;
+        // Percentages are displayed with one decimal and flat stats as
+        // integers.
+        let tolerance = if property.is_percentage() { 0.05 } else { 0.5 };
+        // Allow for float error right at the rounding boundary.
+        decompose_rolls(tiers, value, tolerance + 1e-9)
+            .ok_or_else(|| anyhow!("Unable to decompose {property:?} {value} into rolls"))
     }
 
     pub fn get_affix(&self, id: u32) -> Result<&Affix> {
@@ -162,20 +730,230 @@ impl AnimeGameData {
             .ok_or_else(|| anyhow!("Unable to fetch artifact {id}"))
     }
 
+    pub fn get_artifact_set(&self, id: u32) -> Result<&ArtifactSet> {
+        self.db()?
+            .set_map
+            .get(&id)
+            .ok_or_else(|| anyhow!("Unable to fetch set {id}"))
+    }
+
+    // Expands `count` of an item into base materials through crafting
+    // recipes, with the Mora spent along the way.  Items that can't be
+    // crafted are returned as is.
+    pub fn get_base_materials(&self, id: u32, count: u32) -> Result<Cost> {
+        let db = self.db()?;
+        let mut cost = Cost::default();
+        if let Some(crafting) = db.recipe_map.get(&RecipeKind::Crafting) {
+            expand_crafting(
+                crafting,
+                &db.crafting_output_map,
+                id,
+                count,
+                &mut cost,
+                &mut Vec::new(),
+            )?;
+        } else {
+            cost.materials.insert(id, count);
+        }
+        Ok(cost)
+    }
+
     pub fn get_character(&self, id: u32) -> Result<&String> {
+        Ok(&self.get_character_info(id)?.name)
+    }
+
+    pub fn get_character_ascension_cost(&self, id: u32, from: u32, to: u32) -> Result<Cost> {
+        let character = self.get_character_info(id)?;
+        sum_ascension_cost(
+            &self.db()?.character_ascension_map,
+            character.promote_id,
+            from,
+            to,
+        )
+    }
+
+    pub fn get_character_info(&self, id: u32) -> Result<&Character> {
         self.db()?
             .character_map
             .get(&id)
             .ok_or_else(|| anyhow!("Unable to fetch character {id}"))
     }
 
+    // Levels are fed separately between the character's ascension caps, as
+    // EXP doesn't carry over them.  Mora is charged for all EXP fed.
+    pub fn get_character_level_cost(&self, id: u32, from: u32, to: u32) -> Result<LevelUpCost> {
+        let character = self.get_character_info(id)?;
+        let db = self.db()?;
+        let exps = &db.character_level_exps;
+        if from == 0 || from > to || to as usize > exps.len() + 1 {
+            return Err(anyhow!(
+                "Unable to level character {id} from {from} to {to}"
+            ));
+        }
+        let caps = db
+            .character_ascension_map
+            .get(&character.promote_id)
+            .into_iter()
+            .flatten()
+            .map(|phase| phase.max_level);
+        level_up_cost(
+            exps,
+            &exp_items(&db.material_map, "ITEM_USE_ADD_EXP"),
+            &level_segments(caps, from, to),
+            CHARACTER_EXP_MORA_RATE,
+        )
+    }
+
+    pub fn get_character_profile(&self, id: u32) -> Result<&CharacterProfile> {
+        self.db()?
+            .character_profile_map
+            .get(&id)
+            .ok_or_else(|| anyhow!("Unable to fetch character profile {id}"))
+    }
+
+    // Returns the character's Auto, Skill and Burst ids, in that order.
+    pub fn get_character_skills(&self, id: u32) -> Result<&Vec<u32>> {
+        let depot_id = self.get_character_info(id)?.skill_depot_id;
+        self.db()?
+            .depot_skill_map
+            .get(&depot_id)
+            .ok_or_else(|| anyhow!("Unable to fetch character skills {id}"))
+    }
+
+    pub fn get_character_stats(
+        &self,
+        id: u32,
+        level: u32,
+        ascension: u32,
+    ) -> Result<CharacterStats> {
+        let db = self.db()?;
+        let character = self.get_character_info(id)?;
+        let phase = lookup_ascension(
+            &db.character_ascension_map,
+            character.promote_id,
+            level,
+            ascension,
+        )?;
+        let curve = |name: &str| lookup_curve(&db.character_curve_map, name, level);
+
+        Ok(CharacterStats {
+            hp: character.base_hp * curve(&character.hp_curve)? + phase.hp,
+            attack: character.base_attack * curve(&character.attack_curve)? + phase.attack,
+            defense: character.base_defense * curve(&character.defense_curve)? + phase.defense,
+            bonus: phase.bonus.clone(),
+        })
+    }
+
+    pub fn get_characters_by_birthday(&self, month: u32, day: u32) -> Result<Vec<u32>> {
+        let birthday = Birthday { month, day };
+        Ok(self
+            .db()?
+            .character_profile_map
+            .iter()
+            .filter(|(_, profile)| profile.birthday == Some(birthday))
+            .map(|(id, _)| *id)
+            .collect())
+    }
+
+    // Skill level bonuses are recognised by matching skill names against the
+    // English description, as the tables don't link them.  They are missing
+    // if those names ever change wording.
+    pub fn get_constellations(&self, id: u32) -> Result<&Vec<Constellation>> {
+        let depot_id = self.get_character_info(id)?.skill_depot_id;
+        self.db()?
+            .constellation_map
+            .get(&depot_id)
+            .ok_or_else(|| anyhow!("Unable to fetch constellations {id}"))
+    }
+
+    pub fn get_depot_characters(&self, id: u32) -> Result<&Vec<u32>> {
+        self.db()?
+            .depot_owner_map
+            .get(&id)
+            .ok_or_else(|| anyhow!("Unable to fetch depot characters {id}"))
+    }
+
+    pub fn get_domain(&self, id: u32) -> Result<&Domain> {
+        self.db()?
+            .domain_map
+            .get(&id)
+            .ok_or_else(|| anyhow!("Unable to fetch domain {id}"))
+    }
+
+    pub fn get_domains_on(&self, weekday: Weekday) -> Result<Vec<u32>> {
+        Ok(self
+            .db()?
+            .domain_map
+            .iter()
+            .filter(|(_, domain)| domain.weekdays.contains(&weekday))
+            .map(|(id, _)| *id)
+            .collect())
+    }
+
+    pub fn get_main_stat_options(
+        &self,
+        slot: ArtifactSlot,
+        rarity: u32,
+    ) -> Result<&Vec<MainStatOption>> {
+        self.db()?
+            .main_stat_option_map
+            .get(&slot)
+            .and_then(|rarities| rarities.get(&rarity))
+            .ok_or_else(|| anyhow!("Unable to fetch main stats for {slot:?} of rarity {rarity}"))
+    }
+
+    pub fn get_main_stat_value(&self, rarity: u32, property: Property, level: u32) -> Result<f64> {
+        self.db()?
+            .main_stat_map
+            .get(&rarity)
+            .and_then(|properties| properties.get(&property))
+            .and_then(|levels| levels.get(&level))
+            .copied()
+            .ok_or_else(|| {
+                anyhow!(
+                    "Unable to fetch main stat {property:?} for rarity {rarity} at level {level}"
+                )
+            })
+    }
+
     pub fn get_material(&self, id: u32) -> Result<&String> {
+        Ok(&self.get_material_info(id)?.name)
+    }
+
+    pub fn get_material_info(&self, id: u32) -> Result<&Material> {
         self.db()?
             .material_map
             .get(&id)
             .ok_or_else(|| anyhow!("Unable to fetch material {id}"))
     }
 
+    pub fn get_monster(&self, id: u32) -> Result<&Monster> {
+        self.db()?
+            .monster_map
+            .get(&id)
+            .ok_or_else(|| anyhow!("Unable to fetch monster {id}"))
+    }
+
+    pub fn get_monster_stats(&self, id: u32, level: u32) -> Result<MonsterStats> {
+        let db = self.db()?;
+        let monster = self.get_monster(id)?;
+        let curve = |name: &str| lookup_curve(&db.monster_curve_map, name, level);
+
+        Ok(MonsterStats {
+            hp: monster.base_hp * curve(&monster.hp_curve)?,
+            attack: monster.base_attack * curve(&monster.attack_curve)?,
+            defense: monster.base_defense * curve(&monster.defense_curve)?,
+        })
+    }
+
+    pub fn get_passives(&self, id: u32) -> Result<&Vec<Passive>> {
+        let depot_id = self.get_character_info(id)?.skill_depot_id;
+        self.db()?
+            .passive_map
+            .get(&depot_id)
+            .ok_or_else(|| anyhow!("Unable to fetch passives {id}"))
+    }
+
     pub fn get_property(&self, id: u32) -> Result<&Property> {
         self.db()?
             .property_map
@@ -183,11 +961,53 @@ impl AnimeGameData {
             .ok_or_else(|| anyhow!("Unable to fetch property {id}"))
     }
 
+    pub fn get_recipe(&self, kind: RecipeKind, id: u32) -> Result<&Recipe> {
+        self.db()?
+            .recipe_map
+            .get(&kind)
+            .and_then(|recipes| recipes.get(&id))
+            .ok_or_else(|| anyhow!("Unable to fetch {kind:?} recipe {id}"))
+    }
+
+    pub fn get_recipes_producing(&self, id: u32) -> Result<Vec<(RecipeKind, u32)>> {
+        Ok(self
+            .db()?
+            .recipe_map
+            .iter()
+            .flat_map(|(kind, recipes)| {
+                recipes
+                    .iter()
+                    .filter(|(_, recipe)| recipe.outputs.iter().any(|output| output.id == id))
+                    .map(|(recipe_id, _)| (*kind, *recipe_id))
+            })
+            .collect())
+    }
+
     pub fn get_set(&self, id: u32) -> Result<&String> {
+        Ok(&self.get_artifact_set(id)?.name)
+    }
+
+    pub fn get_skill_characters(&self, id: u32) -> Result<Vec<u32>> {
+        let mut characters: Vec<u32> = self
+            .get_skill_depots(id)?
+            .iter()
+            .filter_map(|depot_id| self.get_depot_characters(*depot_id).ok())
+            .flatten()
+            .copied()
+            .collect();
+        characters.sort();
+        characters.dedup();
+        if characters.is_empty() {
+            return Err(anyhow!("Unable to fetch skill characters {id}"));
+        }
+        Ok(characters)
+    }
+
+    pub fn get_skill_depots(&self, id: u32) -> Result<&Vec<u32>> {
         self.db()?
-            .set_map
+            .skill_owner_map
             .get(&id)
-            .ok_or_else(|| anyhow!("Unable to fetch set {id}"))
+            .ok_or_else(|| anyhow!("Unable to fetch skill depots {id}"))
     }
 
     pub fn get_skill_element(&self, id: u32) -> Result<&Element> {
@@ -197,6 +1017,13 @@ impl AnimeGameData {
             .ok_or_else(|| anyhow!("Unable to fetch skill element {id}"))
     }
 
+    pub fn get_skill_scaling(&self, id: u32) -> Result<&SkillScaling> {
+        self.db()?
+            .skill_scaling_map
+            .get(&id)
+            .ok_or_else(|| anyhow!("Unable to fetch skill scaling {id}"))
+    }
+
     pub fn get_skill_type(&self, id: u32) -> Result<&SkillType> {
         self.db()?
             .skill_type_map
@@ -204,6 +1031,30 @@ impl AnimeGameData {
             .ok_or_else(|| anyhow!("Unable to fetch skill type {id}"))
     }
 
+    pub fn get_substat_tiers(&self, rarity: u32, property: Property) -> Result<&Vec<f64>> {
+        self.db()?
+            .substat_map
+            .get(&rarity)
+            .and_then(|properties| properties.get(&property))
+            .ok_or_else(|| anyhow!("Unable to fetch substat {property:?} for rarity {rarity}"))
+    }
+
+    pub fn get_talent_cost(&self, id: u32, from: u32, to: u32) -> Result<Cost> {
+        let costs = self
+            .db()?
+            .talent_cost_map
+            .get(&id)
+            .ok_or_else(|| anyhow!("Unable to fetch talent costs {id}"))?;
+        if from == 0 || from > to || to as usize > costs.len() + 1 {
+            return Err(anyhow!("Unable to level talent {id} from {from} to {to}"));
+        }
+        let mut cost = Cost::default();
+        for step in &costs[from as usize - 1..to as usize - 1] {
+            cost.add(step);
+        }
+        Ok(cost)
+    }
+
     pub fn get_tps_avatar_id_female(&self) -> Result<u32> {
         self.db()?
             .tps_avatar_id_female
@@ -216,6 +1067,27 @@ impl AnimeGameData {
             .ok_or_else(|| anyhow!("Unable to fetch male TPS avatar id"))
     }
 
+    pub fn get_traveler(&self) -> Result<&Traveler> {
+        self.db()?
+            .traveler
+            .as_ref()
+            .ok_or_else(|| anyhow!("Unable to fetch traveler"))
+    }
+
+    // The TPS avatar ids also refer to the Traveler.
+    pub fn is_traveler(&self, id: u32) -> bool {
+        let Ok(db) = self.db() else {
+            return false;
+        };
+        let traveler_ids = db
+            .traveler
+            .iter()
+            .flat_map(|traveler| [traveler.male_id, traveler.female_id]);
+        traveler_ids
+            .chain([db.tps_avatar_id_male, db.tps_avatar_id_female])
+            .any(|traveler_id| traveler_id == Some(id))
+    }
+
     pub fn get_weapon(&self, id: u32) -> Result<&Weapon> {
         self.db()?
             .weapon_map
@@ -223,6 +1095,91 @@ impl AnimeGameData {
             .ok_or_else(|| anyhow!("Unable to fetch weapon {id}"))
     }
 
+    pub fn get_weapon_ascension_cost(&self, id: u32, from: u32, to: u32) -> Result<Cost> {
+        let weapon = self.get_weapon(id)?;
+        sum_ascension_cost(
+            &self.db()?.weapon_ascension_map,
+            weapon.promote_id,
+            from,
+            to,
+        )
+    }
+
+    // Levels are fed separately between the weapon's ascension caps, as EXP
+    // doesn't carry over them.  Mora is charged for all EXP fed.
+    pub fn get_weapon_level_cost(&self, id: u32, from: u32, to: u32) -> Result<LevelUpCost> {
+        let weapon = self.get_weapon(id)?;
+        let db = self.db()?;
+        let exps = db
+            .weapon_level_exp_map
+            .get(&weapon.rarity)
+            .ok_or_else(|| anyhow!("Unable to fetch weapon level EXP {}", weapon.rarity))?;
+        if from == 0 || from > to || to as usize > exps.len() + 1 {
+            return Err(anyhow!("Unable to level weapon {id} from {from} to {to}"));
+        }
+        let caps = db
+            .weapon_ascension_map
+            .get(&weapon.promote_id)
+            .into_iter()
+            .flatten()
+            .map(|phase| phase.max_level);
+        level_up_cost(
+            exps,
+            &exp_items(&db.material_map, "ITEM_USE_ADD_WEAPON_EXP"),
+            &level_segments(caps, from, to),
+            WEAPON_EXP_MORA_RATE,
+        )
+    }
+
+    pub fn get_weapon_passive(&self, id: u32) -> Result<&WeaponPassive> {
+        self.db()?
+            .weapon_passive_map
+            .get(&id)
+            .ok_or_else(|| anyhow!("Unable to fetch weapon passive {id}"))
+    }
+
+    // Refinements go from 1 to 5.
+    pub fn get_weapon_refinement_cost(&self, id: u32, from: u32, to: u32) -> Result<Cost> {
+        let weapon = self.get_weapon(id)?;
+        if from == 0 || from > to || to as usize > weapon.refinement_mora.len() + 1 {
+            return Err(anyhow!("Unable to refine weapon {id} from {from} to {to}"));
+        }
+        let steps = &weapon.refinement_mora[from as usize - 1..to as usize - 1];
+        let mut cost = Cost {
+            mora: steps.iter().sum(),
+            ..Default::default()
+        };
+        if !steps.is_empty() {
+            let material = weapon.refinement_material.unwrap_or(id);
+            cost.materials.insert(material, steps.len() as u32);
+        }
+        Ok(cost)
+    }
+
+    pub fn get_weapon_stats(&self, id: u32, level: u32, ascension: u32) -> Result<WeaponStats> {
+        let db = self.db()?;
+        let weapon = self.get_weapon(id)?;
+        let phase = lookup_ascension(
+            &db.weapon_ascension_map,
+            weapon.promote_id,
+            level,
+            ascension,
+        )?;
+        let curve = |name: &str| lookup_curve(&db.weapon_curve_map, name, level);
+
+        let secondary = match weapon.secondary_property {
+            Some(property) => Some(make_affix(
+                property,
+                weapon.secondary_base_value * curve(&weapon.secondary_curve)?,
+            )),
+            None => None,
+        };
+        Ok(WeaponStats {
+            attack: weapon.base_attack * curve(&weapon.attack_curve)? + phase.attack,
+            secondary,
+        })
+    }
+
     pub fn has_data(&self) -> bool {
         self.db.is_some()
     }
@@ -259,32 +1216,181 @@ impl AnimeGameData {
         tracing::info!("Downloading text map");
         let text_map = Self::fetch_text_map(source, &latest_git_hash).await?;
 
-        tracing::info!("Downloading skill type map");
-        db.skill_type_map = Self::fetch_skill_type_map(source, &latest_git_hash).await?;
-
-        tracing::info!("Downloading skill element map");
-        db.skill_element_map = Self::fetch_skill_element_map(source, &latest_git_hash).await?;
+        tracing::info!("Downloading skill depot map");
+        let skill_depot_map = Self::fetch_skill_depot_map(source, &latest_git_hash).await?;
+        db.skill_type_map = Self::index_skill_type_map(&skill_depot_map);
+        db.depot_skill_map = Self::index_depot_skill_map(&skill_depot_map);
+        db.skill_owner_map = Self::index_skill_owner_map(&skill_depot_map);
+
+        tracing::info!("Downloading skill map");
+        let skill_map = Self::fetch_skill_map(source, &latest_git_hash).await?;
+        db.skill_element_map = Self::index_skill_element_map(&skill_map);
+
+        tracing::info!("Downloading proud skill map");
+        let proud_skill_map = Self::fetch_proud_skill_map(source, &latest_git_hash).await?;
+        db.skill_scaling_map = Self::index_skill_scaling_map(
+            &text_map,
+            &skill_map,
+            &proud_skill_map,
+            &db.skill_type_map,
+        );
+        db.passive_map = Self::index_passive_map(&text_map, &skill_depot_map, &proud_skill_map);
+        db.talent_cost_map =
+            Self::index_talent_cost_map(&skill_map, &proud_skill_map, &db.skill_type_map);
+
+        tracing::info!("Downloading constellation map");
+        db.constellation_map = Self::fetch_constellation_map(
+            source,
+            &latest_git_hash,
+            &text_map,
+            &skill_depot_map,
+            &skill_map,
+        )
+        .await?;
+
+        tracing::info!("Downloading equip affix map");
+        let equip_affix_map = Self::fetch_equip_affix_map(source, &latest_git_hash).await?;
+
+        tracing::info!("Downloading artifact data");
+        let reliquary_data: Vec<ReliquaryExcelConfigDataEntry> = source
+            .get_json_file(
+                &latest_git_hash,
+                "ExcelBinOutput/ReliquaryExcelConfigData.json",
+            )
+            .await?;
 
         tracing::info!("Downloading set map");
-        db.set_map = Self::fetch_set_map(source, &latest_git_hash, &text_map).await?;
-
-        tracing::info!("Downloading artifact map");
-        db.artifact_map = Self::fetch_artifact_map(source, &latest_git_hash, &db.set_map).await?;
+        db.set_map = Self::fetch_set_map(
+            source,
+            &latest_git_hash,
+            &text_map,
+            &equip_affix_map,
+            &reliquary_data,
+        )
+        .await?;
+        db.artifact_map = Self::index_artifact_map(&reliquary_data, &db.set_map);
 
         tracing::info!("Downloading property map");
-        db.property_map = Self::fetch_property_map(source, &latest_git_hash).await?;
+        let main_prop_data: Vec<ReliquaryMainPropExcelConfigDataEntry> = source
+            .get_json_file(
+                &latest_git_hash,
+                "ExcelBinOutput/ReliquaryMainPropExcelConfigData.json",
+            )
+            .await?;
+        db.property_map = Self::index_property_map(&main_prop_data);
+        db.main_stat_option_map =
+            Self::index_main_stat_option_map(&main_prop_data, &reliquary_data);
+
+        tracing::info!("Downloading main stat map");
+        db.main_stat_map = Self::fetch_main_stat_map(source, &latest_git_hash).await?;
 
         tracing::info!("Downloading affix map");
-        db.affix_map = Self::fetch_affix_map(source, &latest_git_hash).await?;
+        let affix_data: Vec<ReliquaryAffixExcelConfigDataEntry> = source
+            .get_json_file(
+                &latest_git_hash,
+                "ExcelBinOutput/ReliquaryAffixExcelConfigData.json",
+            )
+            .await?;
+        db.affix_map = Self::index_affix_map(&affix_data);
+        db.substat_map = Self::index_substat_map(&reliquary_data, &affix_data);
 
         tracing::info!("Downloading weapon map");
-        db.weapon_map = Self::fetch_weapon_map(source, &latest_git_hash, &text_map).await?;
+        let weapon_data: Vec<WeaponExcelConfigDataEntry> = source
+            .get_json_file(
+                &latest_git_hash,
+                "ExcelBinOutput/WeaponExcelConfigData.json",
+            )
+            .await?;
+        db.weapon_map = Self::index_weapon_map(&text_map, &weapon_data);
+        db.weapon_passive_map =
+            Self::index_weapon_passive_map(&text_map, &weapon_data, &equip_affix_map);
+
+        tracing::info!("Downloading weapon curve map");
+        db.weapon_curve_map = Self::fetch_curve_map(
+            source,
+            &latest_git_hash,
+            "ExcelBinOutput/WeaponCurveExcelConfigData.json",
+        )
+        .await?;
+
+        tracing::info!("Downloading weapon level EXP map");
+        db.weapon_level_exp_map =
+            Self::fetch_weapon_level_exp_map(source, &latest_git_hash).await?;
+
+        tracing::info!("Downloading weapon ascension map");
+        db.weapon_ascension_map =
+            Self::fetch_weapon_ascension_map(source, &latest_git_hash).await?;
 
         tracing::info!("Downloading material map");
         db.material_map = Self::fetch_material_map(source, &latest_git_hash, &text_map).await?;
 
         tracing::info!("Downloading character map");
-        db.character_map = Self::fetch_character_map(source, &latest_git_hash, &text_map).await?;
+        let avatar_data: Vec<AvatarExcelConfigDataEntry> = source
+            .get_json_file(
+                &latest_git_hash,
+                "ExcelBinOutput/AvatarExcelConfigData.json",
+            )
+            .await?;
+        db.character_map = Self::index_character_map(
+            &text_map,
+            &avatar_data,
+            &skill_depot_map,
+            &db.skill_element_map,
+        );
+        db.traveler = Self::index_traveler(
+            &text_map,
+            &avatar_data,
+            &skill_depot_map,
+            &db.skill_element_map,
+        );
+        db.depot_owner_map = Self::index_depot_owner_map(&db.character_map, db.traveler.as_ref());
+
+        tracing::info!("Downloading character profile map");
+        db.character_profile_map =
+            Self::fetch_character_profile_map(source, &latest_git_hash, &text_map).await?;
+
+        tracing::info!("Downloading character curve map");
+        db.character_curve_map = Self::fetch_curve_map(
+            source,
+            &latest_git_hash,
+            "ExcelBinOutput/AvatarCurveExcelConfigData.json",
+        )
+        .await?;
+
+        tracing::info!("Downloading character ascension map");
+        db.character_ascension_map =
+            Self::fetch_character_ascension_map(source, &latest_git_hash).await?;
+
+        tracing::info!("Downloading character level EXP map");
+        db.character_level_exps =
+            Self::fetch_character_level_exps(source, &latest_git_hash).await?;
+
+        tracing::info!("Downloading monster map");
+        db.monster_map = Self::fetch_monster_map(source, &latest_git_hash, &text_map).await?;
+
+        tracing::info!("Downloading monster curve map");
+        db.monster_curve_map = Self::fetch_curve_map(
+            source,
+            &latest_git_hash,
+            "ExcelBinOutput/MonsterCurveExcelConfigData.json",
+        )
+        .await?;
+
+        tracing::info!("Downloading domain map");
+        db.domain_map = Self::fetch_domain_map(
+            source,
+            &latest_git_hash,
+            &text_map,
+            &db.artifact_map,
+            &db.material_map,
+        )
+        .await?;
+
+        tracing::info!("Downloading recipe map");
+        db.recipe_map =
+            Self::fetch_recipe_map(source, &latest_git_hash, &db.material_map, &db.weapon_map)
+                .await?;
+        db.crafting_output_map = Self::index_crafting_output_map(&db.recipe_map);
 
         tracing::info!("Downloading const value map");
         let const_value_map = Self::fetch_const_value_map(source, &latest_git_hash).await?;
@@ -293,7 +1399,8 @@ impl AnimeGameData {
         db.tps_avatar_id_male =
             lookup_const_value(&const_value_map, "CONST_VALUE_TPS_AVATAR_CONFIG_ID_MALE");
 
-        self.db = Some(db);
+        self.db = Some(Arc::new(db));
+        self.load_outcome = LoadOutcome::Updated;
 
         let _ = self.try_save_db();
         Ok(())
@@ -306,78 +1413,162 @@ impl AnimeGameData {
 
         let file = File::create(cache_path)?;
         let writer = BufWriter::new(file);
-        serde_json::to_writer_pretty(writer, &self.db)?;
+        serde_json::to_writer_pretty(writer, &self.db.as_deref())?;
 
         Ok(())
     }
 
-    async fn fetch_affix_map<Source: GameDataSource>(
-        source: &Source,
-        git_ref: &str,
-    ) -> Result<HashMap<u32, Affix>> {
-        let data: Vec<game_data::ReliquaryAffixExcelConfigDataEntry> = source
-            .get_json_file(git_ref, "ExcelBinOutput/ReliquaryAffixExcelConfigData.json")
-            .await?;
-
-        Ok(data
+    fn index_affix_map(affix_data: &[ReliquaryAffixExcelConfigDataEntry]) -> BTreeMap<u32, Affix> {
+        affix_data
             .iter()
             .filter_map(|entry| {
                 let property = entry.prop_type.parse::<Property>().ok()?;
-                let value = if property.is_percentage() {
-                    entry.prop_value * 100.
-                } else {
-                    entry.prop_value
-                };
-                Some((entry.id, Affix { property, value }))
+                Some((entry.id, make_affix(property, entry.prop_value)))
             })
-            .collect())
+            .collect()
     }
 
-    async fn fetch_artifact_map<Source: GameDataSource>(
-        source: &Source,
-        git_ref: &str,
-        set_map: &HashMap<u32, String>,
-    ) -> Result<HashMap<u32, Artifact>> {
-        let data: Vec<game_data::ReliquaryExcelConfigDataEntry> = source
-            .get_json_file(git_ref, "ExcelBinOutput/ReliquaryExcelConfigData.json")
-            .await?;
-
-        let map = data
+    fn index_artifact_map(
+        reliquary_data: &[ReliquaryExcelConfigDataEntry],
+        set_map: &BTreeMap<u32, ArtifactSet>,
+    ) -> BTreeMap<u32, Artifact> {
+        reliquary_data
             .iter()
             .filter_map(|entry| {
-                let set = set_map.get(&entry.set_id?)?.to_string();
+                let set_id = entry.set_id?;
+                let set = set_map.get(&set_id)?.name.clone();
                 let slot = ArtifactSlot::from_game_data_name(&entry.equip_type)?;
                 Some((
                     entry.id,
                     Artifact {
                         set,
+                        set_id,
                         slot,
                         rarity: entry.rank_level,
                     },
                 ))
             })
-            .collect();
+            .collect()
+    }
 
+    async fn fetch_character_ascension_map<Source: GameDataSource>(
+        source: &Source,
+        git_ref: &str,
+    ) -> Result<BTreeMap<u32, Vec<Ascension>>> {
+        let mut data: Vec<AvatarPromoteExcelConfigDataEntry> = source
+            .get_json_file(git_ref, "ExcelBinOutput/AvatarPromoteExcelConfigData.json")
+            .await?;
+        data.sort_by_key(|entry| (entry.avatar_promote_id, entry.promote_level));
+
+        let mut map: BTreeMap<u32, Vec<Ascension>> = BTreeMap::new();
+        for entry in data {
+            map.entry(entry.avatar_promote_id)
+                .or_default()
+                .push(Ascension {
+                    cost: make_cost(entry.scoin_cost, &entry.cost_items),
+                    ..Ascension::from_add_props(entry.unlock_max_level, &entry.add_props)
+                });
+        }
         Ok(map)
     }
 
-    async fn fetch_character_map<Source: GameDataSource>(
+    async fn fetch_character_level_exps<Source: GameDataSource>(
         source: &Source,
         git_ref: &str,
-        text_map: &HashMap<u32, String>,
-    ) -> Result<HashMap<u32, String>> {
-        let data: Vec<AvatarExcelConfigDataEntry> = source
-            .get_json_file(git_ref, "ExcelBinOutput/AvatarExcelConfigData.json")
+    ) -> Result<Vec<u32>> {
+        let mut data: Vec<AvatarLevelExcelConfigDataEntry> = source
+            .get_json_file(git_ref, "ExcelBinOutput/AvatarLevelExcelConfigData.json")
             .await?;
+        data.sort_by_key(|entry| entry.level);
+        Ok(data.iter().map(|entry| entry.exp).collect())
+    }
 
-        Ok(data
+    fn index_character_map(
+        text_map: &HashMap<u32, String>,
+        avatar_data: &[AvatarExcelConfigDataEntry],
+        skill_depot_map: &HashMap<u32, AvatarSkillDepotExcelConfigDataEntry>,
+        skill_element_map: &BTreeMap<u32, Element>,
+    ) -> BTreeMap<u32, Character> {
+        avatar_data
             .iter()
             .filter_map(|entry| {
+                // A character's element is that of their burst, unless they
+                // can switch depots like the Traveler.
+                let element = skill_depot_map
+                    .get(&entry.skill_depot_id)
+                    .filter(|_| entry.cand_skill_depot_ids.is_empty())
+                    .and_then(|depot| depot.energy_skill)
+                    .and_then(|skill| skill_element_map.get(&skill))
+                    .copied();
+                let curve = |prop_type: &str| {
+                    entry
+                        .prop_grow_curves
+                        .iter()
+                        .find(|curve| curve.prop_type == prop_type)
+                        .map(|curve| curve.grow_curve.clone())
+                        .unwrap_or_default()
+                };
                 Some((
                     entry.id,
-                    lookup_text(text_map, entry.name_text_map_hash)?.clone(),
+                    Character {
+                        name: lookup_text(text_map, entry.name_text_map_hash)?.clone(),
+                        rarity: rarity_from_quality_type(&entry.quality_type),
+                        weapon_type: entry.weapon_type.parse().ok(),
+                        element,
+                        body_type: entry.body_type.parse().ok(),
+                        icon_name: entry.icon_name.clone(),
+                        side_icon_name: entry.side_icon_name.clone(),
+                        initial_weapon: entry.initial_weapon,
+                        skill_depot_id: entry.skill_depot_id,
+                        base_hp: entry.hp_base,
+                        base_attack: entry.attack_base,
+                        base_defense: entry.defense_base,
+                        promote_id: entry.avatar_promote_id,
+                        hp_curve: curve("FIGHT_PROP_BASE_HP"),
+                        attack_curve: curve("FIGHT_PROP_BASE_ATTACK"),
+                        defense_curve: curve("FIGHT_PROP_BASE_DEFENSE"),
+                    },
                 ))
             })
+            .collect()
+    }
+
+    async fn fetch_character_profile_map<Source: GameDataSource>(
+        source: &Source,
+        git_ref: &str,
+        text_map: &HashMap<u32, String>,
+    ) -> Result<BTreeMap<u32, CharacterProfile>> {
+        let data: Vec<FetterInfoExcelConfigDataEntry> = source
+            .get_json_file(git_ref, "ExcelBinOutput/FetterInfoExcelConfigData.json")
+            .await?;
+
+        let text = |id: u32| lookup_text(text_map, id).cloned().unwrap_or_default();
+        Ok(data
+            .iter()
+            .map(|entry| {
+                let birthday = (entry.info_birth_month != 0 && entry.info_birth_day != 0)
+                    .then_some(Birthday {
+                        month: entry.info_birth_month,
+                        day: entry.info_birth_day,
+                    });
+                (
+                    entry.avatar_id,
+                    CharacterProfile {
+                        title: text(entry.avatar_title_text_map_hash),
+                        description: text(entry.avatar_detail_text_map_hash),
+                        affiliation: text(entry.avatar_native_text_map_hash),
+                        vision: text(entry.avatar_vision_text_map_hash),
+                        constellation: text(entry.avatar_constellation_text_map_hash),
+                        birthday,
+                        voice_actors: VoiceActors {
+                            chinese: text(entry.cv_chinese_text_map_hash),
+                            japanese: text(entry.cv_japanese_text_map_hash),
+                            english: text(entry.cv_english_text_map_hash),
+                            korean: text(entry.cv_korean_text_map_hash),
+                        },
+                    },
+                )
+            })
             .collect())
     }
 
@@ -395,11 +1586,297 @@ impl AnimeGameData {
             .collect())
     }
 
+    async fn fetch_constellation_map<Source: GameDataSource>(
+        source: &Source,
+        git_ref: &str,
+        text_map: &HashMap<u32, String>,
+        skill_depot_map: &HashMap<u32, AvatarSkillDepotExcelConfigDataEntry>,
+        skill_map: &HashMap<u32, AvatarSkillExcelConfigDataEntry>,
+    ) -> Result<BTreeMap<u32, Vec<Constellation>>> {
+        let data: Vec<AvatarTalentExcelConfigDataEntry> = source
+            .get_json_file(git_ref, "ExcelBinOutput/AvatarTalentExcelConfigData.json")
+            .await?;
+        let talent_map: HashMap<u32, &AvatarTalentExcelConfigDataEntry> =
+            data.iter().map(|entry| (entry.talent_id, entry)).collect();
+
+        let mut map = BTreeMap::new();
+        for depot in skill_depot_map.values() {
+            let skill_names: Vec<(u32, &String)> = (0..3)
+                .filter_map(|index| depot.skill(index))
+                .chain(depot.energy_skill)
+                .filter_map(|id| {
+                    let skill = skill_map.get(&id)?;
+                    Some((id, lookup_text(text_map, skill.name_text_map_hash)?))
+                })
+                .collect();
+
+            let constellations: Vec<Constellation> = depot
+                .talents
+                .iter()
+                .filter_map(|id| talent_map.get(id))
+                .map(|talent| {
+                    let description = lookup_text(text_map, talent.desc_text_map_hash)
+                        .cloned()
+                        .unwrap_or_default();
+                    Constellation {
+                        name: lookup_text(text_map, talent.name_text_map_hash)
+                            .cloned()
+                            .unwrap_or_default(),
+                        skill_level_bonus: skill_level_bonus(
+                            &talent.icon,
+                            &description,
+                            &skill_names,
+                        ),
+                        description,
+                        icon: talent.icon.clone(),
+                    }
+                })
+                .collect();
+            // Depots without a character, e.g. the Traveler's elementless
+            // one, have placeholder talents.
+            if !constellations.is_empty() {
+                map.insert(depot.id, constellations);
+            }
+        }
+        Ok(map)
+    }
+
+    // The lowest recipe id wins if several make the same item.
+    fn index_crafting_output_map(
+        recipe_map: &BTreeMap<RecipeKind, BTreeMap<u32, Recipe>>,
+    ) -> BTreeMap<u32, u32> {
+        let mut map = BTreeMap::new();
+        for (recipe_id, recipe) in recipe_map.get(&RecipeKind::Crafting).into_iter().flatten() {
+            if let [output] = recipe.outputs.as_slice()
+                && output.count > 0
+            {
+                map.entry(output.id).or_insert(*recipe_id);
+            }
+        }
+        map
+    }
+
+    async fn fetch_curve_map<Source: GameDataSource>(
+        source: &Source,
+        git_ref: &str,
+        path: &str,
+    ) -> Result<BTreeMap<String, BTreeMap<u32, f64>>> {
+        let data: Vec<CurveExcelConfigDataEntry> = source.get_json_file(git_ref, path).await?;
+
+        let mut map: BTreeMap<String, BTreeMap<u32, f64>> = BTreeMap::new();
+        for entry in data {
+            for info in entry.curve_infos {
+                map.entry(info.curve_type)
+                    .or_default()
+                    .insert(entry.level, info.value);
+            }
+        }
+        Ok(map)
+    }
+
+    // Only daily domains, i.e. those farmed for artifacts, talent books or
+    // weapon materials, are indexed.
+    async fn fetch_domain_map<Source: GameDataSource>(
+        source: &Source,
+        git_ref: &str,
+        text_map: &HashMap<u32, String>,
+        artifact_map: &BTreeMap<u32, Artifact>,
+        material_map: &BTreeMap<u32, Material>,
+    ) -> Result<BTreeMap<u32, Domain>> {
+        // Entries list the domains behind them for cooldown tips, which is the
+        // only link between the two tables.
+        let entry_data: Vec<DungeonEntryExcelConfigDataEntry> = source
+            .get_json_file(git_ref, "ExcelBinOutput/DungeonEntryExcelConfigData.json")
+            .await?;
+        let mut entrance_map: HashMap<u32, &String> = HashMap::new();
+        for entry in &entry_data {
+            if let Some(name) = lookup_text(text_map, entry.desc_text_map_hash) {
+                for id in &entry.cooldown_tips_dungeon_id {
+                    entrance_map.insert(*id, name);
+                }
+            }
+        }
+
+        let preview_data: Vec<RewardPreviewExcelConfigDataEntry> = source
+            .get_json_file(git_ref, "ExcelBinOutput/RewardPreviewExcelConfigData.json")
+            .await?;
+        let preview_map: HashMap<u32, Vec<u32>> = preview_data
+            .into_iter()
+            .map(|entry| {
+                let items = entry
+                    .preview_items
+                    .iter()
+                    .filter_map(|item| item.id)
+                    .collect();
+                (entry.id, items)
+            })
+            .collect();
+
+        let daily_data: Vec<DailyDungeonConfigDataEntry> = source
+            .get_json_file(git_ref, "ExcelBinOutput/DailyDungeonConfigData.json")
+            .await?;
+        let mut weekday_map: HashMap<u32, Vec<Weekday>> = HashMap::new();
+        for entry in &daily_data {
+            let days = [
+                (Weekday::Monday, &entry.monday),
+                (Weekday::Tuesday, &entry.tuesday),
+                (Weekday::Wednesday, &entry.wednesday),
+                (Weekday::Thursday, &entry.thursday),
+                (Weekday::Friday, &entry.friday),
+                (Weekday::Saturday, &entry.saturday),
+                (Weekday::Sunday, &entry.sunday),
+            ];
+            for (weekday, ids) in days {
+                for id in ids {
+                    let weekdays = weekday_map.entry(*id).or_default();
+                    if !weekdays.contains(&weekday) {
+                        weekdays.push(weekday);
+                    }
+                }
+            }
+        }
+
+        let data: Vec<DungeonExcelConfigDataEntry> = source
+            .get_json_file(git_ref, "ExcelBinOutput/DungeonExcelConfigData.json")
+            .await?;
+
+        Ok(data
+            .iter()
+            .filter_map(|entry| {
+                let rewards = entry
+                    .pass_reward_preview_id
+                    .and_then(|id| preview_map.get(&id))
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+                let mut artifact_sets = Vec::new();
+                let mut materials = Vec::new();
+                for id in rewards {
+                    // Previews list individual artifacts, so map them back to
+                    // their set.
+                    if let Some(artifact) = artifact_map.get(id) {
+                        if !artifact_sets.contains(&artifact.set_id) {
+                            artifact_sets.push(artifact.set_id);
+                        }
+                    } else if material_map
+                        .get(id)
+                        .is_some_and(|material| material.material_type.is_some())
+                    {
+                        // Skips Mora and EXP, which every domain rewards.
+                        materials.push(*id);
+                    }
+                }
+                let mut weekdays = weekday_map.get(&entry.id).cloned().unwrap_or_default();
+                weekdays.sort();
+                Some((
+                    entry.id,
+                    Domain {
+                        name: lookup_text(text_map, entry.name_text_map_hash)?.clone(),
+                        entrance: entrance_map
+                            .get(&entry.id)
+                            .map(|name| (*name).clone())
+                            .unwrap_or_default(),
+                        domain_type: entry.sub_type.parse().ok()?,
+                        recommended_level: entry.show_level,
+                        min_adventure_rank: entry.limit_level,
+                        artifact_sets,
+                        materials,
+                        weekdays,
+                    },
+                ))
+            })
+            .collect())
+    }
+
+    // Entries for each affix are grouped by id and ordered by level, i.e. set
+    // bonus tier or weapon refinement rank.
+    async fn fetch_equip_affix_map<Source: GameDataSource>(
+        source: &Source,
+        git_ref: &str,
+    ) -> Result<HashMap<u32, Vec<EquipAffixExcelConfigDataEntry>>> {
+        let data: Vec<EquipAffixExcelConfigDataEntry> = source
+            .get_json_file(git_ref, "ExcelBinOutput/EquipAffixExcelConfigData.json")
+            .await?;
+
+        let mut map: HashMap<u32, Vec<EquipAffixExcelConfigDataEntry>> = HashMap::new();
+        for entry in data {
+            map.entry(entry.id).or_default().push(entry);
+        }
+        for entries in map.values_mut() {
+            entries.sort_by_key(|entry| entry.level);
+        }
+        Ok(map)
+    }
+
+    async fn fetch_main_stat_map<Source: GameDataSource>(
+        source: &Source,
+        git_ref: &str,
+    ) -> Result<BTreeMap<u32, BTreeMap<Property, BTreeMap<u32, f64>>>> {
+        let data: Vec<ReliquaryLevelExcelConfigDataEntry> = source
+            .get_json_file(git_ref, "ExcelBinOutput/ReliquaryLevelExcelConfigData.json")
+            .await?;
+
+        let mut map: BTreeMap<u32, BTreeMap<Property, BTreeMap<u32, f64>>> = BTreeMap::new();
+        for entry in &data {
+            let Some(rank) = entry.rank else {
+                continue;
+            };
+            // The game counts artifact levels from 1, while players see +0.
+            let level = entry.level.saturating_sub(1);
+            for affix in stats_from_add_props(&entry.add_props) {
+                map.entry(rank)
+                    .or_default()
+                    .entry(affix.property)
+                    .or_default()
+                    .insert(level, affix.value);
+            }
+        }
+        Ok(map)
+    }
+
+    fn index_main_stat_option_map(
+        main_prop_data: &[ReliquaryMainPropExcelConfigDataEntry],
+        reliquary_data: &[ReliquaryExcelConfigDataEntry],
+    ) -> BTreeMap<ArtifactSlot, BTreeMap<u32, Vec<MainStatOption>>> {
+        let mut depots: HashMap<u32, Vec<&ReliquaryMainPropExcelConfigDataEntry>> = HashMap::new();
+        for entry in main_prop_data {
+            depots.entry(entry.prop_depot_id).or_default().push(entry);
+        }
+
+        let mut map: BTreeMap<ArtifactSlot, BTreeMap<u32, Vec<MainStatOption>>> = BTreeMap::new();
+        for artifact in reliquary_data {
+            let Some(slot) = ArtifactSlot::from_game_data_name(&artifact.equip_type) else {
+                continue;
+            };
+            let Some(depot) = depots.get(&artifact.main_prop_depot_id) else {
+                continue;
+            };
+            // Artifacts of the same slot and rarity usually share a depot, so
+            // only add properties that haven't been seen yet.
+            let options = map
+                .entry(slot)
+                .or_default()
+                .entry(artifact.rank_level)
+                .or_default();
+            for entry in depot {
+                let Ok(property) = entry.prop_type.parse::<Property>() else {
+                    continue;
+                };
+                if options.iter().all(|option| option.property != property) {
+                    options.push(MainStatOption {
+                        property,
+                        weight: entry.weight,
+                    });
+                }
+            }
+        }
+        map
+    }
+
     async fn fetch_material_map<Source: GameDataSource>(
         source: &Source,
         git_ref: &str,
         text_map: &HashMap<u32, String>,
-    ) -> Result<HashMap<u32, String>> {
+    ) -> Result<BTreeMap<u32, Material>> {
         let data: Vec<MaterialExcelConfigDataEntry> = source
             .get_json_file(git_ref, "ExcelBinOutput/MaterialExcelConfigData.json")
             .await?;
@@ -407,46 +1884,242 @@ impl AnimeGameData {
         Ok(data
             .iter()
             .filter_map(|entry| {
+                let text = |hash| lookup_text(text_map, hash).cloned().unwrap_or_default();
+                // Unused item use slots are padded with ITEM_USE_NONE, and
+                // params with empty strings.
+                let item_use = entry
+                    .item_use
+                    .iter()
+                    .filter(|item_use| item_use.use_op != "ITEM_USE_NONE")
+                    .map(|item_use| ItemUse {
+                        op: item_use.use_op.clone(),
+                        params: item_use
+                            .use_param
+                            .iter()
+                            .filter(|param| !param.is_empty())
+                            .cloned()
+                            .collect(),
+                    })
+                    .collect();
                 Some((
                     entry.id,
-                    lookup_text(text_map, entry.name_text_map_hash)?.clone(),
+                    Material {
+                        name: lookup_text(text_map, entry.name_text_map_hash)?.clone(),
+                        description: text(entry.desc_text_map_hash),
+                        material_type: entry
+                            .material_type
+                            .as_deref()
+                            .map(MaterialType::from_game_data_name),
+                        item_type: entry.item_type.clone(),
+                        type_description: text(entry.type_desc_text_map_hash),
+                        rarity: entry.rank_level,
+                        icon: entry.icon.clone(),
+                        stack_limit: entry.stack_limit,
+                        item_use,
+                    },
                 ))
             })
             .collect())
     }
 
-    async fn fetch_property_map<Source: GameDataSource>(
+    // Most monsters are named through their describe entry, since their own
+    // name is often an internal one like "Defender".
+    async fn fetch_monster_map<Source: GameDataSource>(
         source: &Source,
         git_ref: &str,
-    ) -> Result<HashMap<u32, Property>> {
-        let data: Vec<ReliquaryMainPropExcelConfigDataEntry> = source
+        text_map: &HashMap<u32, String>,
+    ) -> Result<BTreeMap<u32, Monster>> {
+        let describe_data: Vec<MonsterDescribeExcelConfigDataEntry> = source
             .get_json_file(
                 git_ref,
-                "ExcelBinOutput/ReliquaryMainPropExcelConfigData.json",
+                "ExcelBinOutput/MonsterDescribeExcelConfigData.json",
             )
             .await?;
+        let describe_map: HashMap<u32, MonsterDescribeExcelConfigDataEntry> = describe_data
+            .into_iter()
+            .map(|entry| (entry.id, entry))
+            .collect();
+
+        let data: Vec<MonsterExcelConfigDataEntry> = source
+            .get_json_file(git_ref, "ExcelBinOutput/MonsterExcelConfigData.json")
+            .await?;
 
         Ok(data
             .iter()
-            .filter_map(|entry| Some((entry.id, entry.prop_type.parse::<Property>().ok()?)))
+            .filter_map(|entry| {
+                let describe = entry.describe_id.and_then(|id| describe_map.get(&id));
+                let name = match describe {
+                    Some(describe) => lookup_text(text_map, describe.name_text_map_hash)?,
+                    None => lookup_text(text_map, entry.name_text_map_hash)?,
+                };
+                let curve = |prop_type: &str| {
+                    entry
+                        .prop_grow_curves
+                        .iter()
+                        .find(|curve| curve.prop_type == prop_type)
+                        .map(|curve| curve.grow_curve.clone())
+                        .unwrap_or_default()
+                };
+                Some((
+                    entry.id,
+                    Monster {
+                        name: name.clone(),
+                        icon: describe
+                            .map(|describe| describe.icon.clone())
+                            .unwrap_or_default(),
+                        base_hp: entry.hp_base,
+                        base_attack: entry.attack_base,
+                        base_defense: entry.defense_base,
+                        hp_curve: curve("FIGHT_PROP_BASE_HP"),
+                        attack_curve: curve("FIGHT_PROP_BASE_ATTACK"),
+                        defense_curve: curve("FIGHT_PROP_BASE_DEFENSE"),
+                        resistances: Resistances {
+                            anemo: entry.wind_sub_hurt * 100.,
+                            geo: entry.rock_sub_hurt * 100.,
+                            electro: entry.elec_sub_hurt * 100.,
+                            hydro: entry.water_sub_hurt * 100.,
+                            pyro: entry.fire_sub_hurt * 100.,
+                            cryo: entry.ice_sub_hurt * 100.,
+                            dendro: entry.grass_sub_hurt * 100.,
+                            physical: entry.physical_sub_hurt * 100.,
+                        },
+                    },
+                ))
+            })
             .collect())
     }
 
-    async fn fetch_set_map<Source: GameDataSource>(
+    fn index_property_map(
+        main_prop_data: &[ReliquaryMainPropExcelConfigDataEntry],
+    ) -> BTreeMap<u32, Property> {
+        main_prop_data
+            .iter()
+            .filter_map(|entry| Some((entry.id, entry.prop_type.parse::<Property>().ok()?)))
+            .collect()
+    }
+
+    // Entries for each proud skill group are ordered by level.
+    async fn fetch_proud_skill_map<Source: GameDataSource>(
         source: &Source,
         git_ref: &str,
-        text_map: &HashMap<u32, String>,
-    ) -> Result<HashMap<u32, String>> {
-        let affix_data: Vec<game_data::EquipAffixExcelConfigDataEntry> = source
-            .get_json_file(git_ref, "ExcelBinOutput/EquipAffixExcelConfigData.json")
+    ) -> Result<HashMap<u32, Vec<ProudSkillExcelConfigDataEntry>>> {
+        let data: Vec<ProudSkillExcelConfigDataEntry> = source
+            .get_json_file(git_ref, "ExcelBinOutput/ProudSkillExcelConfigData.json")
             .await?;
 
-        // An affix has one entry per set bonus tier, all sharing the set's name.
-        let affix_names: HashMap<u32, &String> = affix_data
-            .iter()
-            .filter_map(|entry| Some((entry.id, lookup_text(text_map, entry.name_text_map_hash)?)))
-            .collect();
+        let mut map: HashMap<u32, Vec<ProudSkillExcelConfigDataEntry>> = HashMap::new();
+        for entry in data {
+            map.entry(entry.proud_skill_group_id)
+                .or_default()
+                .push(entry);
+        }
+        for entries in map.values_mut() {
+            entries.sort_by_key(|entry| entry.level);
+        }
+        Ok(map)
+    }
+
+    // Items are named after materials, or weapons for forging.  Recipes with
+    // items that can't be named are skipped.
+    async fn fetch_recipe_map<Source: GameDataSource>(
+        source: &Source,
+        git_ref: &str,
+        material_map: &BTreeMap<u32, Material>,
+        weapon_map: &BTreeMap<u32, Weapon>,
+    ) -> Result<BTreeMap<RecipeKind, BTreeMap<u32, Recipe>>> {
+        let item = |id: u32, count: u32| {
+            let name = match material_map.get(&id) {
+                Some(material) => &material.name,
+                None => &weapon_map.get(&id)?.name,
+            };
+            Some(RecipeItem {
+                id,
+                name: name.clone(),
+                count,
+            })
+        };
+        let items = |entries: &[ItemCountEntry]| {
+            entries
+                .iter()
+                .filter_map(|entry| entry.id.map(|id| item(id, entry.count)))
+                .collect::<Option<Vec<_>>>()
+        };
+
+        let mut map: BTreeMap<RecipeKind, BTreeMap<u32, Recipe>> = BTreeMap::new();
+
+        let combine_data: Vec<CombineExcelConfigDataEntry> = source
+            .get_json_file(git_ref, "ExcelBinOutput/CombineExcelConfigData.json")
+            .await?;
+        for entry in &combine_data {
+            let kind = match entry.recipe_type.as_str() {
+                "RECIPE_TYPE_CONVERT" => RecipeKind::Conversion,
+                _ => RecipeKind::Crafting,
+            };
+            let (Some(inputs), Some(output)) = (
+                items(&entry.material_items),
+                item(entry.result_item_id, entry.result_item_count),
+            ) else {
+                continue;
+            };
+            map.entry(kind).or_default().insert(
+                entry.combine_id,
+                Recipe {
+                    inputs,
+                    outputs: vec![output],
+                    mora: entry.scoin_cost,
+                },
+            );
+        }
+
+        let forge_data: Vec<ForgeExcelConfigDataEntry> = source
+            .get_json_file(git_ref, "ExcelBinOutput/ForgeExcelConfigData.json")
+            .await?;
+        for entry in &forge_data {
+            let (Some(inputs), Some(output)) = (
+                items(&entry.material_items),
+                item(entry.result_item_id, entry.result_item_count),
+            ) else {
+                continue;
+            };
+            map.entry(RecipeKind::Forging).or_default().insert(
+                entry.id,
+                Recipe {
+                    inputs,
+                    outputs: vec![output],
+                    mora: entry.scoin_cost,
+                },
+            );
+        }
+
+        let cook_data: Vec<CookRecipeExcelConfigDataEntry> = source
+            .get_json_file(git_ref, "ExcelBinOutput/CookRecipeExcelConfigData.json")
+            .await?;
+        for entry in &cook_data {
+            let (Some(inputs), Some(outputs)) =
+                (items(&entry.input_vec), items(&entry.quality_output_vec))
+            else {
+                continue;
+            };
+            map.entry(RecipeKind::Cooking).or_default().insert(
+                entry.id,
+                Recipe {
+                    inputs,
+                    outputs,
+                    mora: 0,
+                },
+            );
+        }
+
+        Ok(map)
+    }
 
+    async fn fetch_set_map<Source: GameDataSource>(
+        source: &Source,
+        git_ref: &str,
+        text_map: &HashMap<u32, String>,
+        equip_affix_map: &HashMap<u32, Vec<EquipAffixExcelConfigDataEntry>>,
+        reliquary_data: &[ReliquaryExcelConfigDataEntry],
+    ) -> Result<BTreeMap<u32, ArtifactSet>> {
         let set_data: Vec<game_data::ReliquarySetExcelConfigDataEntry> = source
             .get_json_file(git_ref, "ExcelBinOutput/ReliquarySetExcelConfigData.json")
             .await?;
@@ -455,44 +2128,229 @@ impl AnimeGameData {
             .iter()
             .filter_map(|entry| {
                 // Sets that grant no bonuses omit equipAffixId and have no name.
-                let name = *affix_names.get(&entry.equip_affix_id?)?;
-                Some((entry.set_id, name.clone()))
+                let affixes = equip_affix_map.get(&entry.equip_affix_id?)?;
+
+                // An affix has one entry per set bonus tier, all sharing the
+                // set's name.
+                let name = affixes
+                    .iter()
+                    .find_map(|affix| lookup_text(text_map, affix.name_text_map_hash))?;
+                let bonuses = entry
+                    .set_need_num
+                    .iter()
+                    .zip(affixes)
+                    .map(|(pieces, affix)| SetBonus {
+                        pieces: *pieces,
+                        description: lookup_text(text_map, affix.desc_text_map_hash)
+                            .cloned()
+                            .unwrap_or_default(),
+                        stats: stats_from_add_props(&affix.add_props),
+                        params: affix.param_list.clone(),
+                    })
+                    .collect();
+                // containsList only holds the pieces shown in the set preview,
+                // so collect every artifact that belongs to the set instead.
+                let artifacts = reliquary_data
+                    .iter()
+                    .filter(|artifact| artifact.set_id == Some(entry.set_id))
+                    .map(|artifact| artifact.id)
+                    .collect();
+                Some((
+                    entry.set_id,
+                    ArtifactSet {
+                        name: name.clone(),
+                        bonuses,
+                        artifacts,
+                    },
+                ))
             })
             .collect())
     }
 
-    async fn fetch_skill_element_map<Source: GameDataSource>(
+    async fn fetch_skill_map<Source: GameDataSource>(
         source: &Source,
         git_ref: &str,
-    ) -> Result<HashMap<u32, Element>> {
+    ) -> Result<HashMap<u32, AvatarSkillExcelConfigDataEntry>> {
         let data: Vec<AvatarSkillExcelConfigDataEntry> = source
             .get_json_file(git_ref, "ExcelBinOutput/AvatarSkillExcelConfigData.json")
             .await?;
 
-        Ok(data
-            .iter()
+        Ok(data.into_iter().map(|entry| (entry.id, entry)).collect())
+    }
+
+    fn index_skill_element_map(
+        skill_map: &HashMap<u32, AvatarSkillExcelConfigDataEntry>,
+    ) -> BTreeMap<u32, Element> {
+        skill_map
+            .values()
             .filter_map(|entry| {
                 // Skills that cost no elemental energy omit costElemType and
                 // have no element.
                 let element = entry.cost_elem_type.as_ref()?.parse::<Element>().ok()?;
                 Some((entry.id, element))
             })
-            .collect())
+            .collect()
     }
 
-    async fn fetch_skill_type_map<Source: GameDataSource>(
+    async fn fetch_skill_depot_map<Source: GameDataSource>(
         source: &Source,
         git_ref: &str,
-    ) -> Result<HashMap<u32, SkillType>> {
-        let data: Vec<game_data::AvatarSkillDepotExcelConfigDataEntry> = source
+    ) -> Result<HashMap<u32, AvatarSkillDepotExcelConfigDataEntry>> {
+        let data: Vec<AvatarSkillDepotExcelConfigDataEntry> = source
             .get_json_file(
                 git_ref,
                 "ExcelBinOutput/AvatarSkillDepotExcelConfigData.json",
             )
             .await?;
 
-        let mut type_map = HashMap::new();
-        for config in data {
+        Ok(data.into_iter().map(|entry| (entry.id, entry)).collect())
+    }
+
+    fn index_depot_owner_map(
+        character_map: &BTreeMap<u32, Character>,
+        traveler: Option<&Traveler>,
+    ) -> BTreeMap<u32, Vec<u32>> {
+        let mut map: BTreeMap<u32, Vec<u32>> = BTreeMap::new();
+        for (id, character) in character_map {
+            map.entry(character.skill_depot_id).or_default().push(*id);
+        }
+        // The Traveler also owns the depots they can switch to.
+        for variant in traveler.iter().flat_map(|traveler| &traveler.variants) {
+            let owners = map.entry(variant.depot_id).or_default();
+            if !owners.contains(&variant.character_id) {
+                owners.push(variant.character_id);
+            }
+        }
+        map
+    }
+
+    fn index_depot_skill_map(
+        skill_depot_map: &HashMap<u32, AvatarSkillDepotExcelConfigDataEntry>,
+    ) -> BTreeMap<u32, Vec<u32>> {
+        skill_depot_map
+            .values()
+            .map(|depot| {
+                let skills = [depot.skill(0), depot.skill(1), depot.energy_skill]
+                    .into_iter()
+                    .flatten()
+                    .collect();
+                (depot.id, skills)
+            })
+            .collect()
+    }
+
+    fn index_passive_map(
+        text_map: &HashMap<u32, String>,
+        skill_depot_map: &HashMap<u32, AvatarSkillDepotExcelConfigDataEntry>,
+        proud_skill_map: &HashMap<u32, Vec<ProudSkillExcelConfigDataEntry>>,
+    ) -> BTreeMap<u32, Vec<Passive>> {
+        let mut map = BTreeMap::new();
+        for depot in skill_depot_map.values() {
+            let passives: Vec<Passive> = depot
+                .inherent_proud_skill_opens
+                .iter()
+                .filter_map(|open| {
+                    // Unused slots are padded with group 0.
+                    let entry = proud_skill_map.get(&open.proud_skill_group_id)?.first()?;
+                    Some(Passive {
+                        name: lookup_text(text_map, entry.name_text_map_hash)?.clone(),
+                        description: lookup_text(text_map, entry.desc_text_map_hash)
+                            .cloned()
+                            .unwrap_or_default(),
+                        icon: entry.icon.clone(),
+                        unlock_ascension: open.need_avatar_promote_level,
+                        stats: stats_from_add_props(&entry.add_props),
+                    })
+                })
+                .collect();
+            if !passives.is_empty() {
+                map.insert(depot.id, passives);
+            }
+        }
+        map
+    }
+
+    // Only skills with a type, i.e. a character's Auto, Skill and Burst, have
+    // levels to scale with.
+    fn index_skill_scaling_map(
+        text_map: &HashMap<u32, String>,
+        skill_map: &HashMap<u32, AvatarSkillExcelConfigDataEntry>,
+        proud_skill_map: &HashMap<u32, Vec<ProudSkillExcelConfigDataEntry>>,
+        skill_type_map: &BTreeMap<u32, SkillType>,
+    ) -> BTreeMap<u32, SkillScaling> {
+        skill_type_map
+            .keys()
+            .filter_map(|id| {
+                let group_id = skill_map.get(id)?.proud_skill_group_id?;
+                let entries = proud_skill_map.get(&group_id)?;
+                // Labels are shared by all levels, and unused slots have no
+                // text.
+                let labels = entries
+                    .first()?
+                    .param_desc_list
+                    .iter()
+                    .filter_map(|hash| lookup_text(text_map, *hash))
+                    .filter(|text| !text.is_empty())
+                    .map(|text| {
+                        let (name, format) = text.split_once('|').unwrap_or((text, ""));
+                        ParamLabel {
+                            name: name.to_string(),
+                            format: format.to_string(),
+                        }
+                    })
+                    .collect();
+                let levels = entries
+                    .iter()
+                    .map(|entry| entry.param_list.clone())
+                    .collect();
+                Some((*id, SkillScaling { labels, levels }))
+            })
+            .collect()
+    }
+
+    // Some skills, e.g. the Traveler's Normal Attack, are shared by several
+    // depots.
+    fn index_skill_owner_map(
+        skill_depot_map: &HashMap<u32, AvatarSkillDepotExcelConfigDataEntry>,
+    ) -> BTreeMap<u32, Vec<u32>> {
+        let mut map: BTreeMap<u32, Vec<u32>> = BTreeMap::new();
+        for depot in skill_depot_map.values() {
+            for skill in depot.all_skills() {
+                map.entry(skill).or_default().push(depot.id);
+            }
+        }
+        for depots in map.values_mut() {
+            depots.sort();
+            depots.dedup();
+        }
+        map
+    }
+
+    fn index_talent_cost_map(
+        skill_map: &HashMap<u32, AvatarSkillExcelConfigDataEntry>,
+        proud_skill_map: &HashMap<u32, Vec<ProudSkillExcelConfigDataEntry>>,
+        skill_type_map: &BTreeMap<u32, SkillType>,
+    ) -> BTreeMap<u32, Vec<Cost>> {
+        skill_type_map
+            .keys()
+            .filter_map(|id| {
+                let group_id = skill_map.get(id)?.proud_skill_group_id?;
+                let entries = proud_skill_map.get(&group_id)?;
+                // The max level has nothing left to pay for.
+                let costs = entries[..entries.len().saturating_sub(1)]
+                    .iter()
+                    .map(|entry| make_cost(entry.coin_cost, &entry.cost_items))
+                    .collect();
+                Some((*id, costs))
+            })
+            .collect()
+    }
+
+    fn index_skill_type_map(
+        skill_depot_map: &HashMap<u32, AvatarSkillDepotExcelConfigDataEntry>,
+    ) -> BTreeMap<u32, SkillType> {
+        let mut type_map = BTreeMap::new();
+        for config in skill_depot_map.values() {
             // Depots without a burst (e.g. the elementless Traveler) have no
             // energy skill, and skill slots may be empty or missing.
             if let Some(skill) = config.energy_skill {
@@ -506,7 +2364,59 @@ impl AnimeGameData {
             }
         }
 
-        Ok(type_map)
+        type_map
+    }
+
+    fn index_substat_map(
+        reliquary_data: &[ReliquaryExcelConfigDataEntry],
+        affix_data: &[ReliquaryAffixExcelConfigDataEntry],
+    ) -> BTreeMap<u32, BTreeMap<Property, Vec<f64>>> {
+        let depot_rarities: BTreeMap<u32, u32> = reliquary_data
+            .iter()
+            .map(|entry| (entry.append_prop_depot_id, entry.rank_level))
+            .collect();
+
+        let mut depots: BTreeMap<u32, BTreeMap<Property, Vec<f64>>> = BTreeMap::new();
+        for entry in affix_data {
+            if !depot_rarities.contains_key(&entry.depot_id) {
+                continue;
+            }
+            let Ok(property) = entry.prop_type.parse::<Property>() else {
+                continue;
+            };
+            let affix = make_affix(property, entry.prop_value);
+            depots
+                .entry(entry.depot_id)
+                .or_default()
+                .entry(property)
+                .or_default()
+                .push(affix.value);
+        }
+        for tiers in depots
+            .values_mut()
+            .flat_map(|properties| properties.values_mut())
+        {
+            tiers.sort_by(f64::total_cmp);
+            tiers.dedup();
+        }
+
+        // Every depot of a rarity rolls the same tiers in the game data, so
+        // they are merged by rarity.  A depot that disagrees is skipped in
+        // favour of the lowest depot id rather than mixing their tiers.
+        let mut map: BTreeMap<u32, BTreeMap<Property, Vec<f64>>> = BTreeMap::new();
+        for (depot, tiers) in depots {
+            let rarity = depot_rarities[&depot];
+            match map.get(&rarity) {
+                None => {
+                    map.insert(rarity, tiers);
+                }
+                Some(existing) if *existing != tiers => {
+                    tracing::warn!("Skipping substat depot {depot} with mismatched tiers");
+                }
+                Some(_) => {}
+            }
+        }
+        map
     }
 
     async fn fetch_text_map<Source: GameDataSource>(
@@ -518,39 +2428,180 @@ impl AnimeGameData {
             .await
     }
 
-    async fn fetch_weapon_map<Source: GameDataSource>(
+    // The Traveler is the only character with candidate skill depots.
+    fn index_traveler(
+        text_map: &HashMap<u32, String>,
+        avatar_data: &[AvatarExcelConfigDataEntry],
+        skill_depot_map: &HashMap<u32, AvatarSkillDepotExcelConfigDataEntry>,
+        skill_element_map: &BTreeMap<u32, Element>,
+    ) -> Option<Traveler> {
+        let mut traveler: Option<Traveler> = None;
+        for entry in avatar_data
+            .iter()
+            .filter(|entry| !entry.cand_skill_depot_ids.is_empty())
+        {
+            let Some(name) = lookup_text(text_map, entry.name_text_map_hash) else {
+                continue;
+            };
+            let traveler = traveler.get_or_insert_with(|| Traveler {
+                name: name.clone(),
+                male_id: None,
+                female_id: None,
+                variants: Vec::new(),
+            });
+            match entry.body_type.parse::<BodyType>() {
+                Ok(BodyType::Boy) => traveler.male_id = Some(entry.id),
+                Ok(BodyType::Girl) => traveler.female_id = Some(entry.id),
+                _ => continue,
+            }
+            for depot_id in &entry.cand_skill_depot_ids {
+                let Some(depot) = skill_depot_map.get(depot_id) else {
+                    continue;
+                };
+                traveler.variants.push(TravelerVariant {
+                    character_id: entry.id,
+                    depot_id: *depot_id,
+                    element: depot
+                        .energy_skill
+                        .and_then(|skill| skill_element_map.get(&skill))
+                        .copied(),
+                    skills: [depot.skill(0), depot.skill(1), depot.energy_skill]
+                        .into_iter()
+                        .flatten()
+                        .collect(),
+                });
+            }
+        }
+        traveler
+    }
+
+    async fn fetch_weapon_ascension_map<Source: GameDataSource>(
         source: &Source,
         git_ref: &str,
-        text_map: &HashMap<u32, String>,
-    ) -> Result<HashMap<u32, Weapon>> {
-        let data: Vec<WeaponExcelConfigDataEntry> = source
-            .get_json_file(git_ref, "ExcelBinOutput/WeaponExcelConfigData.json")
+    ) -> Result<BTreeMap<u32, Vec<Ascension>>> {
+        let mut data: Vec<WeaponPromoteExcelConfigDataEntry> = source
+            .get_json_file(git_ref, "ExcelBinOutput/WeaponPromoteExcelConfigData.json")
             .await?;
+        data.sort_by_key(|entry| (entry.weapon_promote_id, entry.promote_level));
+
+        let mut map: BTreeMap<u32, Vec<Ascension>> = BTreeMap::new();
+        for entry in data {
+            map.entry(entry.weapon_promote_id)
+                .or_default()
+                .push(Ascension {
+                    cost: make_cost(entry.coin_cost, &entry.cost_items),
+                    ..Ascension::from_add_props(entry.unlock_max_level, &entry.add_props)
+                });
+        }
+        Ok(map)
+    }
 
-        Ok(data
+    async fn fetch_weapon_level_exp_map<Source: GameDataSource>(
+        source: &Source,
+        git_ref: &str,
+    ) -> Result<BTreeMap<u32, Vec<u32>>> {
+        let mut data: Vec<WeaponLevelExcelConfigDataEntry> = source
+            .get_json_file(git_ref, "ExcelBinOutput/WeaponLevelExcelConfigData.json")
+            .await?;
+        data.sort_by_key(|entry| entry.level);
+
+        let mut map: BTreeMap<u32, Vec<u32>> = BTreeMap::new();
+        // The max level omits requiredExps.
+        for entry in data.iter().filter(|entry| !entry.required_exps.is_empty()) {
+            for (index, exp) in entry.required_exps.iter().enumerate() {
+                map.entry(index as u32 + 1).or_default().push(*exp);
+            }
+        }
+        Ok(map)
+    }
+
+    fn index_weapon_map(
+        text_map: &HashMap<u32, String>,
+        weapon_data: &[WeaponExcelConfigDataEntry],
+    ) -> BTreeMap<u32, Weapon> {
+        weapon_data
             .iter()
             .filter_map(|entry| {
                 let name = lookup_text(text_map, entry.name_text_map_hash)?;
+                let attack = entry
+                    .weapon_prop
+                    .iter()
+                    .find(|prop| prop.prop_type.as_deref() == Some("FIGHT_PROP_BASE_ATTACK"));
+                let secondary = entry.weapon_prop.iter().find_map(|prop| {
+                    let property = prop.prop_type.as_ref()?.parse::<Property>().ok()?;
+                    Some((property, prop))
+                });
                 Some((
                     entry.id,
                     Weapon {
                         name: name.clone(),
                         rarity: entry.rank_level,
+                        weapon_type: entry.weapon_type.parse().ok(),
+                        base_attack: attack.map(|prop| prop.init_value).unwrap_or_default(),
+                        attack_curve: attack
+                            .map(|prop| prop.curve_type.clone())
+                            .unwrap_or_default(),
+                        secondary_property: secondary.map(|(property, _)| property),
+                        secondary_base_value: secondary
+                            .map(|(_, prop)| prop.init_value)
+                            .unwrap_or_default(),
+                        secondary_curve: secondary
+                            .map(|(_, prop)| prop.curve_type.clone())
+                            .unwrap_or_default(),
+                        promote_id: entry.weapon_promote_id,
+                        base_exp: entry.weapon_base_exp,
+                        refinement_mora: entry.awaken_costs.clone(),
+                        refinement_material: Some(entry.awaken_material)
+                            .filter(|&material| material != 0),
                     },
                 ))
             })
-            .collect())
-    }
-}
-
-impl Default for AnimeGameData {
-    fn default() -> Self {
-        Self::new()
+            .collect()
     }
-}
 
-#[cfg(test)]
-mod tests {
+    fn index_weapon_passive_map(
+        text_map: &HashMap<u32, String>,
+        weapon_data: &[WeaponExcelConfigDataEntry],
+        equip_affix_map: &HashMap<u32, Vec<EquipAffixExcelConfigDataEntry>>,
+    ) -> BTreeMap<u32, WeaponPassive> {
+        weapon_data
+            .iter()
+            .filter_map(|entry| {
+                // Unused affix slots hold 0, and weapons without a passive
+                // have none.
+                let affix_id = entry.skill_affix.iter().find(|&&id| id != 0)?;
+                let entries = equip_affix_map.get(affix_id)?;
+                let name = lookup_text(text_map, entries.first()?.name_text_map_hash)?;
+                let refinements = entries
+                    .iter()
+                    .map(|entry| WeaponRefinement {
+                        description: lookup_text(text_map, entry.desc_text_map_hash)
+                            .cloned()
+                            .unwrap_or_default(),
+                        stats: stats_from_add_props(&entry.add_props),
+                        params: entry.param_list.clone(),
+                    })
+                    .collect();
+                Some((
+                    entry.id,
+                    WeaponPassive {
+                        name: name.clone(),
+                        refinements,
+                    },
+                ))
+            })
+            .collect()
+    }
+}
+
+impl Default for AnimeGameData {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
     use anyhow::anyhow;
     use tempfile::NamedTempFile;
 
@@ -575,12 +2626,54 @@ mod tests {
                 "ExcelBinOutput/ReliquaryExcelConfigData.json" => {
                     include_str!("test_data/ExcelBinOutput/ReliquaryExcelConfigData.json")
                 }
+                "ExcelBinOutput/AvatarCurveExcelConfigData.json" => {
+                    include_str!("test_data/ExcelBinOutput/AvatarCurveExcelConfigData.json")
+                }
                 "ExcelBinOutput/AvatarExcelConfigData.json" => {
                     include_str!("test_data/ExcelBinOutput/AvatarExcelConfigData.json")
                 }
+                "ExcelBinOutput/AvatarLevelExcelConfigData.json" => {
+                    include_str!("test_data/ExcelBinOutput/AvatarLevelExcelConfigData.json")
+                }
+                "ExcelBinOutput/AvatarPromoteExcelConfigData.json" => {
+                    include_str!("test_data/ExcelBinOutput/AvatarPromoteExcelConfigData.json")
+                }
+                "ExcelBinOutput/DailyDungeonConfigData.json" => {
+                    include_str!("test_data/ExcelBinOutput/DailyDungeonConfigData.json")
+                }
+                "ExcelBinOutput/DungeonEntryExcelConfigData.json" => {
+                    include_str!("test_data/ExcelBinOutput/DungeonEntryExcelConfigData.json")
+                }
+                "ExcelBinOutput/DungeonExcelConfigData.json" => {
+                    include_str!("test_data/ExcelBinOutput/DungeonExcelConfigData.json")
+                }
+                "ExcelBinOutput/FetterInfoExcelConfigData.json" => {
+                    include_str!("test_data/ExcelBinOutput/FetterInfoExcelConfigData.json")
+                }
+                "ExcelBinOutput/ForgeExcelConfigData.json" => {
+                    include_str!("test_data/ExcelBinOutput/ForgeExcelConfigData.json")
+                }
                 "ExcelBinOutput/MaterialExcelConfigData.json" => {
                     include_str!("test_data/ExcelBinOutput/MaterialExcelConfigData.json")
                 }
+                "ExcelBinOutput/AvatarTalentExcelConfigData.json" => {
+                    include_str!("test_data/ExcelBinOutput/AvatarTalentExcelConfigData.json")
+                }
+                "ExcelBinOutput/MonsterCurveExcelConfigData.json" => {
+                    include_str!("test_data/ExcelBinOutput/MonsterCurveExcelConfigData.json")
+                }
+                "ExcelBinOutput/MonsterDescribeExcelConfigData.json" => {
+                    include_str!("test_data/ExcelBinOutput/MonsterDescribeExcelConfigData.json")
+                }
+                "ExcelBinOutput/MonsterExcelConfigData.json" => {
+                    include_str!("test_data/ExcelBinOutput/MonsterExcelConfigData.json")
+                }
+                "ExcelBinOutput/ProudSkillExcelConfigData.json" => {
+                    include_str!("test_data/ExcelBinOutput/ProudSkillExcelConfigData.json")
+                }
+                "ExcelBinOutput/ReliquaryLevelExcelConfigData.json" => {
+                    include_str!("test_data/ExcelBinOutput/ReliquaryLevelExcelConfigData.json")
+                }
                 "ExcelBinOutput/ReliquaryMainPropExcelConfigData.json" => {
                     include_str!("test_data/ExcelBinOutput/ReliquaryMainPropExcelConfigData.json")
                 }
@@ -596,12 +2689,30 @@ mod tests {
                 "ExcelBinOutput/AvatarSkillExcelConfigData.json" => {
                     include_str!("test_data/ExcelBinOutput/AvatarSkillExcelConfigData.json")
                 }
+                "ExcelBinOutput/CombineExcelConfigData.json" => {
+                    include_str!("test_data/ExcelBinOutput/CombineExcelConfigData.json")
+                }
                 "ExcelBinOutput/ConstValueExcelConfigData.json" => {
                     include_str!("test_data/ExcelBinOutput/ConstValueExcelConfigData.json")
                 }
+                "ExcelBinOutput/CookRecipeExcelConfigData.json" => {
+                    include_str!("test_data/ExcelBinOutput/CookRecipeExcelConfigData.json")
+                }
+                "ExcelBinOutput/RewardPreviewExcelConfigData.json" => {
+                    include_str!("test_data/ExcelBinOutput/RewardPreviewExcelConfigData.json")
+                }
+                "ExcelBinOutput/WeaponCurveExcelConfigData.json" => {
+                    include_str!("test_data/ExcelBinOutput/WeaponCurveExcelConfigData.json")
+                }
                 "ExcelBinOutput/WeaponExcelConfigData.json" => {
                     include_str!("test_data/ExcelBinOutput/WeaponExcelConfigData.json")
                 }
+                "ExcelBinOutput/WeaponLevelExcelConfigData.json" => {
+                    include_str!("test_data/ExcelBinOutput/WeaponLevelExcelConfigData.json")
+                }
+                "ExcelBinOutput/WeaponPromoteExcelConfigData.json" => {
+                    include_str!("test_data/ExcelBinOutput/WeaponPromoteExcelConfigData.json")
+                }
                 "TextMap/TextMap_MediumEN.json" => {
                     include_str!("test_data/TextMap/TextMap_MediumEN.json")
                 }
@@ -632,93 +2743,838 @@ mod tests {
         }
     }
 
-    // A source whose const value data is missing the entries indexed below.
-    struct TestDataSource3;
+    // A source whose const value data is missing the entries indexed below.
+    struct TestDataSource3;
+
+    impl GameDataSource for TestDataSource3 {
+        async fn get_latest_hash(&self) -> Result<String> {
+            Ok("13be4fd7343fe4cee8fa0096fe854b1c5b01b124-3".into())
+        }
+
+        async fn get_json_file<T: DeserializeOwned>(&self, git_ref: &str, path: &str) -> Result<T> {
+            let json_string = match path {
+                "ExcelBinOutput/ConstValueExcelConfigData.json" => {
+                    include_str!("test_data/ExcelBinOutput/ConstValueExcelConfigData2.json")
+                }
+                _ => return TestDataSource {}.get_json_file(git_ref, path).await,
+            };
+            let data = serde_json::from_str(json_string)?;
+            Ok(data)
+        }
+    }
+
+    // Records each requested path before serving it from the test data.
+    #[derive(Default)]
+    struct TestDataSource4 {
+        paths: std::sync::Mutex<Vec<String>>,
+    }
+
+    impl GameDataSource for TestDataSource4 {
+        async fn get_latest_hash(&self) -> Result<String> {
+            TestDataSource {}.get_latest_hash().await
+        }
+
+        async fn get_json_file<T: DeserializeOwned>(&self, git_ref: &str, path: &str) -> Result<T> {
+            self.paths.lock().unwrap().push(path.to_string());
+            TestDataSource {}.get_json_file(git_ref, path).await
+        }
+    }
+
+    #[tokio::test]
+    async fn character_map_returns_correct_character() {
+        let source = TestDataSource;
+        let mut data = AnimeGameData::new();
+        data.update_impl(&source).await.unwrap();
+        assert_eq!(data.get_character(10000061).unwrap(), &"Kirara".to_string());
+    }
+
+    #[tokio::test]
+    async fn character_map_returns_correct_character_info() {
+        let source = TestDataSource;
+        let mut data = AnimeGameData::new();
+        data.update_impl(&source).await.unwrap();
+        assert_eq!(
+            data.get_character_info(10000061).unwrap(),
+            &Character {
+                name: "Kirara".into(),
+                rarity: Some(4),
+                weapon_type: Some(WeaponType::Sword),
+                element: Some(Element::Dendro),
+                body_type: Some(BodyType::Girl),
+                icon_name: "UI_AvatarIcon_Momoka".into(),
+                side_icon_name: "UI_AvatarIcon_Side_Momoka".into(),
+                initial_weapon: 11101,
+                skill_depot_id: 6101,
+                base_hp: 1021.20593,
+                base_attack: 18.6984,
+                base_defense: 45.78,
+                promote_id: 61,
+                hp_curve: "GROW_CURVE_HP_S4".into(),
+                attack_curve: "GROW_CURVE_ATTACK_S4".into(),
+                defense_curve: "GROW_CURVE_HP_S4".into(),
+            }
+        );
+
+        // Avatars with unknown types are still indexed.
+        assert_eq!(data.get_character(10000901).unwrap(), "Manekin");
+        let manekin = data.get_character_info(10000901).unwrap();
+        assert_eq!(manekin.rarity, None);
+        assert_eq!(manekin.weapon_type, None);
+        assert_eq!(manekin.body_type, None);
+    }
+
+    #[tokio::test]
+    async fn character_stats_scale_with_level_and_ascension() {
+        let source = TestDataSource;
+        let mut data = AnimeGameData::new();
+        data.update_impl(&source).await.unwrap();
+
+        assert_eq!(
+            data.get_character_stats(10000061, 1, 0).unwrap(),
+            CharacterStats {
+                hp: 1021.20593,
+                attack: 18.6984,
+                defense: 45.78,
+                bonus: Some(Affix {
+                    property: Property::HpPercent,
+                    value: 0.0
+                }),
+            }
+        );
+
+        // Ascending adds flat stats on top of the curve scaled base stats.
+        assert_eq!(
+            data.get_character_stats(10000061, 20, 1).unwrap(),
+            CharacterStats {
+                hp: 1021.20593 * 2.541 + 783.78,
+                attack: 18.6984 * 2.593 + 14.33,
+                defense: 45.78 * 2.541 + 35.14,
+                bonus: Some(Affix {
+                    property: Property::HpPercent,
+                    value: 0.0
+                }),
+            }
+        );
+        assert_eq!(
+            data.get_character_stats(10000061, 90, 6).unwrap(),
+            CharacterStats {
+                hp: 1021.20593 * 10.775 + 3754.95,
+                attack: 18.6984 * 10.841 + 68.73,
+                defense: 45.78 * 10.775 + 168.53,
+                bonus: Some(Affix {
+                    property: Property::HpPercent,
+                    value: 24.0
+                }),
+            }
+        );
+
+        // Levels outside of the ascension phase are rejected.
+        assert!(data.get_character_stats(10000061, 40, 0).is_err());
+        assert!(data.get_character_stats(10000061, 1, 1).is_err());
+        assert!(data.get_character_stats(10000061, 90, 7).is_err());
+    }
+
+    #[tokio::test]
+    async fn skill_type_map_returns_correct_type() {
+        let source = TestDataSource;
+        let mut data = AnimeGameData::new();
+        data.update_impl(&source).await.unwrap();
+        assert_eq!(data.get_skill_type(10024).unwrap(), &SkillType::Auto);
+        assert_eq!(data.get_skill_type(10018).unwrap(), &SkillType::Skill);
+        assert_eq!(data.get_skill_type(10019).unwrap(), &SkillType::Burst);
+    }
+
+    #[tokio::test]
+    async fn skill_element_map_returns_correct_element() {
+        let source = TestDataSource;
+        let mut data = AnimeGameData::new();
+        data.update_impl(&source).await.unwrap();
+
+        assert_eq!(data.get_skill_element(10034).unwrap(), &Element::Anemo);
+        assert_eq!(data.get_skill_element(10078).unwrap(), &Element::Geo);
+        assert_eq!(data.get_skill_element(10014).unwrap(), &Element::Electro);
+        assert_eq!(data.get_skill_element(10072).unwrap(), &Element::Hydro);
+        assert_eq!(data.get_skill_element(10017).unwrap(), &Element::Pyro);
+        assert_eq!(data.get_skill_element(10019).unwrap(), &Element::Cryo);
+        assert_eq!(data.get_skill_element(10118).unwrap(), &Element::Dendro);
+    }
+
+    #[tokio::test]
+    async fn skill_element_map_skips_skills_with_omitted_cost_elem_type() {
+        let source = TestDataSource;
+        let mut data = AnimeGameData::new();
+        data.update_impl(&source).await.unwrap();
+
+        // Normal attacks and skills that cost no elemental energy omit
+        // costElemType and are not indexed.
+        assert!(data.get_skill_element(10024).is_err());
+        assert!(data.get_skill_element(10018).is_err());
+    }
+
+    #[tokio::test]
+    async fn skill_type_map_handles_omitted_energy_skill() {
+        let source = TestDataSource;
+        let mut data = AnimeGameData::new();
+        data.update_impl(&source).await.unwrap();
+
+        // Depot 501 (elementless Traveler) has no energySkill field and only
+        // a normal attack.  Its normal attack is still indexed...
+        assert_eq!(data.get_skill_type(100540).unwrap(), &SkillType::Auto);
+
+        // ...but the defaulted (0) energy skill and empty skill slots are not.
+        assert!(data.get_skill_type(0).is_err());
+    }
+
+    #[tokio::test]
+    async fn set_map_skips_sets_without_equip_affix() {
+        let source = TestDataSource;
+        let mut data = AnimeGameData::new();
+        data.update_impl(&source).await.unwrap();
+
+        // Set 15000 grants no bonuses and omits equipAffixId.
+        assert!(data.get_set(15000).is_err());
+    }
+
+    #[tokio::test]
+    async fn artifact_set_returns_bonuses_and_members() {
+        let source = TestDataSource;
+        let mut data = AnimeGameData::new();
+        data.update_impl(&source).await.unwrap();
+
+        let set = data.get_artifact_set(15031).unwrap();
+        assert_eq!(set.name, "Marechaussee Hunter");
+        // The 5-star circlet is missing from the set's containsList.
+        assert_eq!(set.artifacts, vec![31534, 31432, 31442]);
+        assert_eq!(
+            set.bonuses,
+            vec![
+                SetBonus {
+                    pieces: 2,
+                    description: "CRIT Rate +36%.".into(),
+                    stats: vec![Affix {
+                        property: Property::CritRate,
+                        value: 36.0
+                    }],
+                    params: vec![0.36],
+                },
+                SetBonus {
+                    pieces: 4,
+                    description: "When current HP increases or decreases, CRIT Rate will be \
+                                  increased by 12% for 5s. Max 3 stacks."
+                        .into(),
+                    stats: vec![],
+                    params: vec![0.12],
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn main_stat_values_scale_with_level() {
+        let source = TestDataSource;
+        let mut data = AnimeGameData::new();
+        data.update_impl(&source).await.unwrap();
+
+        assert_eq!(data.get_main_stat_value(5, Property::Hp, 0).unwrap(), 717.0);
+        assert_eq!(
+            data.get_main_stat_value(5, Property::Hp, 20).unwrap(),
+            4780.0
+        );
+
+        // Percentages are scaled like affixes.
+        let crit_rate = data.get_main_stat_value(5, Property::CritRate, 20).unwrap();
+        assert!((crit_rate - 31.1).abs() < 1e-9);
+
+        assert!(data.get_main_stat_value(5, Property::Hp, 21).is_err());
+        assert!(
+            data.get_main_stat_value(5, Property::CritDamage, 0)
+                .is_err()
+        );
+        assert!(data.get_main_stat_value(4, Property::Hp, 0).is_err());
+    }
+
+    #[tokio::test]
+    async fn main_stat_options_depend_on_slot_and_rarity() {
+        let source = TestDataSource;
+        let mut data = AnimeGameData::new();
+        data.update_impl(&source).await.unwrap();
+
+        let options = data
+            .get_main_stat_options(ArtifactSlot::Circlet, 5)
+            .unwrap();
+        let properties: Vec<Property> = options.iter().map(|option| option.property).collect();
+        assert_eq!(
+            properties,
+            vec![
+                Property::HpPercent,
+                Property::AttackPercent,
+                Property::DefensePercent,
+                Property::CritRate,
+                Property::CritDamage,
+                Property::Healing,
+                Property::ElementalMastery,
+            ]
+        );
+        assert_eq!(options[0].weight, Some(1100));
+        assert_eq!(options[6].weight, None);
+
+        let options = data.get_main_stat_options(ArtifactSlot::Plume, 1).unwrap();
+        assert_eq!(
+            options,
+            &vec![MainStatOption {
+                property: Property::Attack,
+                weight: Some(1000)
+            }]
+        );
+
+        assert!(data.get_main_stat_options(ArtifactSlot::Sands, 4).is_err());
+        assert!(data.get_main_stat_options(ArtifactSlot::Flower, 5).is_err());
+    }
+
+    #[tokio::test]
+    async fn substat_tiers_are_sorted_per_rarity() {
+        let source = TestDataSource;
+        let mut data = AnimeGameData::new();
+        data.update_impl(&source).await.unwrap();
+
+        assert_eq!(
+            data.get_substat_tiers(5, Property::Hp).unwrap(),
+            &vec![209.13, 239.0, 268.88, 298.75]
+        );
+        assert_eq!(
+            data.get_substat_tiers(5, Property::CritRate).unwrap().len(),
+            4
+        );
+
+        // Depot 982 isn't used by any artifact.
+        assert!(data.get_substat_tiers(5, Property::GeoDamage).is_err());
+    }
+
+    #[test]
+    fn substat_depots_of_a_rarity_must_agree() {
+        let reliquary_data: Vec<ReliquaryExcelConfigDataEntry> =
+            serde_json::from_value(serde_json::json!([
+                { "appendPropDepotId": 501, "rankLevel": 5 },
+                { "appendPropDepotId": 502, "rankLevel": 5 },
+                { "appendPropDepotId": 503, "rankLevel": 5 },
+            ]))
+            .unwrap();
+        let affix_data: Vec<ReliquaryAffixExcelConfigDataEntry> =
+            serde_json::from_value(serde_json::json!([
+                { "depotId": 501, "propType": "FIGHT_PROP_HP", "propValue": 209.13 },
+                { "depotId": 502, "propType": "FIGHT_PROP_HP", "propValue": 209.13 },
+                { "depotId": 503, "propType": "FIGHT_PROP_HP", "propValue": 100.0 },
+            ]))
+            .unwrap();
+
+        let map = AnimeGameData::index_substat_map(&reliquary_data, &affix_data);
+        assert_eq!(map[&5][&Property::Hp], vec![209.13]);
+    }
+
+    #[tokio::test]
+    async fn decompose_substat_prefers_fewest_closest_rolls() {
+        let source = TestDataSource;
+        let mut data = AnimeGameData::new();
+        data.update_impl(&source).await.unwrap();
+
+        let rolls = data.decompose_substat(5, Property::CritRate, 3.9).unwrap();
+        assert_eq!(rolls.len(), 1);
+        assert!((rolls[0] - 3.89).abs() < 1e-9);
+
+        let rolls = data.decompose_substat(5, Property::CritRate, 7.8).unwrap();
+        assert_eq!(rolls.len(), 2);
+        assert!(rolls.iter().all(|roll| (roll - 3.89).abs() < 1e-9));
+
+        // 209.13 + 268.88 also rounds to 478, but is further off.
+        assert_eq!(
+            data.decompose_substat(5, Property::Hp, 478.).unwrap(),
+            vec![239., 239.]
+        );
+
+        // Below the lowest tier and above six max rolls.
+        assert!(data.decompose_substat(5, Property::CritRate, 1.0).is_err());
+        assert!(data.decompose_substat(5, Property::CritRate, 25.0).is_err());
+    }
+
+    #[tokio::test]
+    async fn constellations_detect_skill_level_bonuses() {
+        let source = TestDataSource;
+        let mut data = AnimeGameData::new();
+        data.update_impl(&source).await.unwrap();
+
+        let constellations = data.get_constellations(10000061).unwrap();
+        assert_eq!(constellations.len(), 6);
+        assert_eq!(constellations[0].name, "Material Hoarder");
+        assert_eq!(constellations[0].icon, "UI_Talent_S_Momoka_01");
+
+        // C1 mentions the burst but doesn't level it up.
+        assert_eq!(constellations[0].skill_level_bonus, None);
+        assert_eq!(
+            constellations[2].skill_level_bonus,
+            Some(SkillLevelBonus {
+                skill_id: 10612,
+                levels: 3
+            })
+        );
+        assert_eq!(
+            constellations[4].skill_level_bonus,
+            Some(SkillLevelBonus {
+                skill_id: 10615,
+                levels: 3
+            })
+        );
+        assert!(
+            constellations[4]
+                .description
+                .starts_with("Increases the Level of Secret Art: Surprise Dispatch by 3.")
+        );
+    }
+
+    #[tokio::test]
+    async fn skill_scaling_returns_params_per_level() {
+        let source = TestDataSource;
+        let mut data = AnimeGameData::new();
+        data.update_impl(&source).await.unwrap();
+
+        let scaling = data.get_skill_scaling(10612).unwrap();
+        assert_eq!(scaling.levels.len(), 15);
+        assert_eq!(
+            scaling.labels[1],
+            ParamLabel {
+                name: "Shield DMG Absorption".into(),
+                format: "{param3:F1P} Max HP+{param4:I}".into(),
+            }
+        );
+        assert_eq!(scaling.labels.len(), 3);
+        assert_eq!(scaling.levels[0][0], 1.04);
+        assert_eq!(scaling.levels[14][0], 2.47);
+
+        assert_eq!(data.get_skill_scaling(10611).unwrap().labels.len(), 2);
+        assert_eq!(data.get_skill_scaling(10615).unwrap().levels[0][4], 60.);
+
+        // The Traveler's elementless depot has a typed placeholder skill
+        // without a proud skill group.
+        assert!(data.get_skill_scaling(100540).is_err());
+    }
+
+    #[tokio::test]
+    async fn passives_list_unlock_ascension() {
+        let source = TestDataSource;
+        let mut data = AnimeGameData::new();
+        data.update_impl(&source).await.unwrap();
+
+        let passives = data.get_passives(10000061).unwrap();
+        let unlocks: Vec<(&str, u32)> = passives
+            .iter()
+            .map(|passive| (passive.name.as_str(), passive.unlock_ascension))
+            .collect();
+        // The utility passive omits needAvatarPromoteLevel, and the empty
+        // trailing slots are skipped.
+        assert_eq!(
+            unlocks,
+            vec![
+                ("Bewitching, Betwitching Tails", 1),
+                ("Cat's Creeping Carriage", 4),
+                ("Pupillary Variance", 0),
+            ]
+        );
+        assert_eq!(passives[2].icon, "UI_Talent_Explosion_Stamina");
+        assert!(passives[1].stats.is_empty());
+    }
+
+    #[tokio::test]
+    async fn skills_map_back_to_their_characters() {
+        let source = TestDataSource;
+        let mut data = AnimeGameData::new();
+        data.update_impl(&source).await.unwrap();
+
+        assert_eq!(
+            data.get_character_skills(10000061).unwrap(),
+            &vec![10611, 10612, 10615]
+        );
+        assert_eq!(data.get_skill_depots(10612).unwrap(), &vec![6101]);
+        assert_eq!(data.get_depot_characters(6101).unwrap(), &vec![10000061]);
+        assert_eq!(data.get_skill_characters(10615).unwrap(), vec![10000061]);
+
+        // Ayaka's alternate sprint belongs to her depot, but her character
+        // isn't in the test data.
+        assert_eq!(data.get_skill_depots(10013).unwrap(), &vec![201]);
+        assert!(data.get_skill_characters(10013).is_err());
+        assert!(data.get_skill_depots(0).is_err());
+    }
+
+    #[tokio::test]
+    async fn traveler_lists_variants_for_both_body_types() {
+        let source = TestDataSource;
+        let mut data = AnimeGameData::new();
+        data.update_impl(&source).await.unwrap();
+
+        let traveler = data.get_traveler().unwrap();
+        assert_eq!(traveler.name, "Traveler");
+        assert_eq!(traveler.male_id, Some(10000005));
+        assert_eq!(traveler.female_id, Some(10000007));
+        assert_eq!(traveler.variants.len(), 4);
+        assert_eq!(
+            traveler.variants[1],
+            TravelerVariant {
+                character_id: 10000005,
+                depot_id: 504,
+                element: Some(Element::Geo),
+                skills: vec![100540, 10077, 10078],
+            }
+        );
+        // The elementless depot has only a Normal Attack.
+        assert_eq!(traveler.variants[2].element, None);
+
+        // The Traveler's element depends on the chosen variant.
+        assert_eq!(data.get_character_info(10000005).unwrap().element, None);
+
+        assert!(data.is_traveler(10000005));
+        assert!(data.is_traveler(10000007));
+        assert!(data.is_traveler(10000134));
+        assert!(!data.is_traveler(10000061));
+
+        // Both body types share the burst.
+        assert_eq!(
+            data.get_skill_characters(10078).unwrap(),
+            vec![10000005, 10000007]
+        );
+    }
+
+    #[tokio::test]
+    async fn ascension_cost_sums_phases() {
+        let source = TestDataSource;
+        let mut data = AnimeGameData::new();
+        data.update_impl(&source).await.unwrap();
+
+        assert_eq!(
+            data.get_character_ascension_cost(10000061, 0, 2).unwrap(),
+            Cost {
+                mora: 60000,
+                materials: BTreeMap::from([
+                    (101217, 13),
+                    (104141, 1),
+                    (104142, 3),
+                    (112081, 18),
+                    (113046, 2),
+                ]),
+            }
+        );
+        assert_eq!(
+            data.get_character_ascension_cost(10000061, 3, 3).unwrap(),
+            Cost::default()
+        );
+        assert!(data.get_character_ascension_cost(10000061, 2, 1).is_err());
+        assert!(data.get_character_ascension_cost(10000061, 0, 7).is_err());
+
+        let cost = data.get_weapon_ascension_cost(11505, 0, 6).unwrap();
+        assert_eq!(cost.mora, 225000);
+        assert_eq!(cost.materials[&112001], 23);
+    }
+
+    #[tokio::test]
+    async fn character_level_cost_feeds_books_per_ascension() {
+        let source = TestDataSource;
+        let mut data = AnimeGameData::new();
+        data.update_impl(&source).await.unwrap();
+
+        assert_eq!(
+            data.get_character_level_cost(10000061, 1, 20).unwrap(),
+            LevelUpCost {
+                exp: 120175,
+                cost: Cost {
+                    mora: 24200,
+                    materials: BTreeMap::from([(104001, 1), (104003, 6)]),
+                },
+            }
+        );
+
+        // Levels 15 to 20 and 20 to 25 are fed separately, so this takes one
+        // Hero's Wit less than pooling the EXP would.
+        assert_eq!(
+            data.get_character_level_cost(10000061, 15, 25).unwrap(),
+            LevelUpCost {
+                exp: 148275,
+                cost: Cost {
+                    mora: 29800,
+                    materials: BTreeMap::from([(104001, 9), (104002, 4), (104003, 6)]),
+                },
+            }
+        );
+
+        assert!(data.get_character_level_cost(10000061, 1, 91).is_err());
+        assert!(data.get_character_level_cost(10000061, 20, 1).is_err());
+    }
+
+    #[tokio::test]
+    async fn talent_cost_sums_levels() {
+        let source = TestDataSource;
+        let mut data = AnimeGameData::new();
+        data.update_impl(&source).await.unwrap();
 
-    impl GameDataSource for TestDataSource3 {
-        async fn get_latest_hash(&self) -> Result<String> {
-            Ok("13be4fd7343fe4cee8fa0096fe854b1c5b01b124-3".into())
-        }
+        let cost = data.get_talent_cost(10612, 1, 10).unwrap();
+        assert_eq!(cost.mora, 1652500);
+        assert_eq!(cost.materials[&104301], 3);
+        assert_eq!(cost.materials[&104302], 21);
+        assert_eq!(cost.materials[&104303], 38);
+        assert_eq!(cost.materials[&113049], 6);
+        assert_eq!(cost.materials[&104319], 1);
 
-        async fn get_json_file<T: DeserializeOwned>(&self, git_ref: &str, path: &str) -> Result<T> {
-            let json_string = match path {
-                "ExcelBinOutput/ConstValueExcelConfigData.json" => {
-                    include_str!("test_data/ExcelBinOutput/ConstValueExcelConfigData2.json")
-                }
-                _ => return TestDataSource {}.get_json_file(git_ref, path).await,
-            };
-            let data = serde_json::from_str(json_string)?;
-            Ok(data)
-        }
+        assert_eq!(
+            data.get_talent_cost(10615, 1, 2).unwrap(),
+            Cost {
+                mora: 12500,
+                materials: BTreeMap::from([(104301, 3), (112059, 6)]),
+            }
+        );
+
+        // Levels past 10 come from constellations and cost nothing.
+        assert_eq!(
+            data.get_talent_cost(10611, 10, 15).unwrap(),
+            Cost::default()
+        );
+        assert!(data.get_talent_cost(10611, 0, 2).is_err());
+        assert!(data.get_talent_cost(10611, 1, 16).is_err());
     }
 
     #[tokio::test]
-    async fn character_map_returns_correct_character() {
+    async fn weapon_refinement_cost_uses_copies() {
         let source = TestDataSource;
         let mut data = AnimeGameData::new();
         data.update_impl(&source).await.unwrap();
-        assert_eq!(data.get_character(10000061).unwrap(), &"Kirara".to_string());
+
+        assert_eq!(
+            data.get_weapon_refinement_cost(11505, 1, 5).unwrap(),
+            Cost {
+                mora: 30000,
+                materials: BTreeMap::from([(11505, 4)]),
+            }
+        );
+        assert_eq!(
+            data.get_weapon_refinement_cost(11505, 3, 3).unwrap(),
+            Cost::default()
+        );
+        assert!(data.get_weapon_refinement_cost(11505, 1, 6).is_err());
     }
 
     #[tokio::test]
-    async fn skill_type_map_returns_correct_type() {
+    async fn weapon_level_cost_feeds_ores() {
         let source = TestDataSource;
         let mut data = AnimeGameData::new();
         data.update_impl(&source).await.unwrap();
-        assert_eq!(data.get_skill_type(10024).unwrap(), &SkillType::Auto);
-        assert_eq!(data.get_skill_type(10018).unwrap(), &SkillType::Skill);
-        assert_eq!(data.get_skill_type(10019).unwrap(), &SkillType::Burst);
+
+        // 20255 EXP is covered by two Mystic Enhancement Ores and one
+        // Enhancement Ore, overshooting by 145.
+        assert_eq!(
+            data.get_weapon_level_cost(11505, 1, 20).unwrap(),
+            LevelUpCost {
+                exp: 20255,
+                cost: Cost {
+                    mora: 2040,
+                    materials: BTreeMap::from([(104011, 1), (104013, 2)]),
+                },
+            }
+        );
+        // Levels 15 to 20 and 20 to 25 are fed separately, so this takes one
+        // more Enhancement Ore than pooling the EXP would.
+        assert_eq!(
+            data.get_weapon_level_cost(11505, 15, 25).unwrap(),
+            LevelUpCost {
+                exp: 17880,
+                cost: Cost {
+                    mora: 1840,
+                    materials: BTreeMap::from([(104011, 6), (104012, 3), (104013, 1)]),
+                },
+            }
+        );
+        assert_eq!(
+            data.get_weapon_level_cost(11505, 1, 90).unwrap().exp,
+            1777395
+        );
+        assert!(data.get_weapon_level_cost(11505, 1, 91).is_err());
+        assert!(data.get_weapon_level_cost(11505, 20, 1).is_err());
     }
 
     #[tokio::test]
-    async fn skill_element_map_returns_correct_element() {
+    async fn character_profile_returns_lore_info() {
         let source = TestDataSource;
         let mut data = AnimeGameData::new();
         data.update_impl(&source).await.unwrap();
 
-        assert_eq!(data.get_skill_element(10034).unwrap(), &Element::Anemo);
-        assert_eq!(data.get_skill_element(10078).unwrap(), &Element::Geo);
-        assert_eq!(data.get_skill_element(10014).unwrap(), &Element::Electro);
-        assert_eq!(data.get_skill_element(10072).unwrap(), &Element::Hydro);
-        assert_eq!(data.get_skill_element(10017).unwrap(), &Element::Pyro);
-        assert_eq!(data.get_skill_element(10019).unwrap(), &Element::Cryo);
-        assert_eq!(data.get_skill_element(10118).unwrap(), &Element::Dendro);
+        assert_eq!(
+            data.get_character_profile(10000061).unwrap(),
+            &CharacterProfile {
+                title: "Cat on the Eaves".to_string(),
+                description: "A nekomata youkai who works at Komaniya Express as a courier."
+                    .to_string(),
+                affiliation: "Komaniya Express".to_string(),
+                vision: "Dendro".to_string(),
+                constellation: "Arcella".to_string(),
+                birthday: Some(Birthday { month: 1, day: 22 }),
+                voice_actors: VoiceActors {
+                    chinese: "Hua Ling".to_string(),
+                    japanese: "Rina Sato".to_string(),
+                    english: "Erika Harlacher".to_string(),
+                    korean: "Kim Ha-yeong".to_string(),
+                },
+            }
+        );
+
+        let traveler = data.get_character_profile(10000005).unwrap();
+        assert_eq!(traveler.birthday, None);
+        assert_eq!(traveler.vision, "");
+
+        assert_eq!(
+            data.get_characters_by_birthday(1, 22).unwrap(),
+            vec![10000061]
+        );
+        assert!(data.get_characters_by_birthday(1, 23).unwrap().is_empty());
+        assert!(data.get_character_profile(10000002).is_err());
     }
 
     #[tokio::test]
-    async fn skill_element_map_skips_skills_with_omitted_cost_elem_type() {
+    async fn monster_map_returns_resistances_and_stats() {
         let source = TestDataSource;
         let mut data = AnimeGameData::new();
         data.update_impl(&source).await.unwrap();
 
-        // Normal attacks and skills that cost no elemental energy omit
-        // costElemType and are not indexed.
-        assert!(data.get_skill_element(10024).is_err());
-        assert!(data.get_skill_element(10018).is_err());
+        assert_eq!(
+            data.get_monster(20050201).unwrap(),
+            &Monster {
+                name: "Ruin Guard".to_string(),
+                icon: "UI_MonsterIcon_Defender_None".to_string(),
+                base_hp: 300.0,
+                base_attack: 50.0,
+                base_defense: 500.0,
+                hp_curve: "GROW_CURVE_HP".to_string(),
+                attack_curve: "GROW_CURVE_ATTACK".to_string(),
+                defense_curve: "GROW_CURVE_DEFENSE".to_string(),
+                resistances: Resistances {
+                    anemo: 10.0,
+                    geo: 10.0,
+                    electro: 10.0,
+                    hydro: 10.0,
+                    pyro: 10.0,
+                    cryo: 10.0,
+                    dendro: 10.0,
+                    physical: 70.0,
+                },
+            }
+        );
+        assert_eq!(data.get_monster(21010101).unwrap().name, "Hilichurl");
+
+        assert_eq!(
+            data.get_monster_stats(21010101, 90).unwrap(),
+            MonsterStats {
+                hp: 100.0 * 205.4,
+                attack: 20.0 * 18.6,
+                defense: 500.0 * 1.9,
+            }
+        );
+        assert!(data.get_monster_stats(21010101, 50).is_err());
+
+        // Monster 29010101 has no describe entry and an unknown name.
+        assert!(data.get_monster(29010101).is_err());
     }
 
     #[tokio::test]
-    async fn skill_type_map_handles_omitted_energy_skill() {
+    async fn domain_map_returns_drops_and_weekdays() {
         let source = TestDataSource;
         let mut data = AnimeGameData::new();
         data.update_impl(&source).await.unwrap();
 
-        // Depot 501 (elementless Traveler) has no energySkill field and only
-        // a normal attack.  Its normal attack is still indexed...
-        assert_eq!(data.get_skill_type(100540).unwrap(), &SkillType::Auto);
+        assert_eq!(
+            data.get_domain(5103).unwrap(),
+            &Domain {
+                name: "Domain of Mastery: Frondstone IV".to_string(),
+                entrance: "Violet Court".to_string(),
+                domain_type: DomainType::TalentBook,
+                recommended_level: 80,
+                min_adventure_rank: 36,
+                artifact_sets: vec![],
+                materials: vec![104301],
+                weekdays: vec![Weekday::Monday, Weekday::Thursday, Weekday::Sunday],
+            }
+        );
 
-        // ...but the defaulted (0) energy skill and empty skill slots are not.
-        assert!(data.get_skill_type(0).is_err());
+        let domain = data.get_domain(5501).unwrap();
+        assert_eq!(domain.domain_type, DomainType::Artifact);
+        assert_eq!(domain.entrance, "Denouement of Sin");
+        assert_eq!(domain.artifact_sets, vec![15031, 15048]);
+        assert!(domain.materials.is_empty());
+        assert_eq!(domain.weekdays.len(), 7);
+
+        assert_eq!(
+            data.get_domains_on(Weekday::Monday).unwrap(),
+            vec![5103, 5501]
+        );
+        assert_eq!(data.get_domains_on(Weekday::Tuesday).unwrap(), vec![5501]);
+
+        // Dungeon 1001 is a story domain.
+        assert!(data.get_domain(1001).is_err());
     }
 
     #[tokio::test]
-    async fn set_map_skips_sets_without_equip_affix() {
+    async fn recipe_map_returns_recipes_and_base_materials() {
         let source = TestDataSource;
         let mut data = AnimeGameData::new();
         data.update_impl(&source).await.unwrap();
 
-        // Set 15000 grants no bonuses and omits equipAffixId.
-        assert!(data.get_set(15000).is_err());
+        assert_eq!(
+            data.get_recipe(RecipeKind::Crafting, 1011).unwrap(),
+            &Recipe {
+                inputs: vec![RecipeItem {
+                    id: 104301,
+                    name: "Teachings of Transience".to_string(),
+                    count: 3,
+                }],
+                outputs: vec![RecipeItem {
+                    id: 104302,
+                    name: "Guide to Transience".to_string(),
+                    count: 1,
+                }],
+                mora: 1750,
+            }
+        );
+        assert_eq!(
+            data.get_recipe(RecipeKind::Forging, 1001).unwrap().outputs[0].name,
+            "Mystic Enhancement Ore"
+        );
+        let cooking = data.get_recipe(RecipeKind::Cooking, 1001).unwrap();
+        assert_eq!(cooking.inputs[0].name, "Sunsettia");
+        assert_eq!(cooking.outputs.len(), 3);
+        // Combine 3001 yields an unknown item.
+        assert!(data.get_recipe(RecipeKind::Crafting, 3001).is_err());
+        assert!(data.get_recipe(RecipeKind::Conversion, 1011).is_err());
+
+        assert_eq!(
+            data.get_recipes_producing(104301).unwrap(),
+            vec![(RecipeKind::Conversion, 2001)]
+        );
+
+        // Conversions are not followed, so Teachings stay as they are.
+        assert_eq!(
+            data.get_base_materials(104303, 2).unwrap(),
+            Cost {
+                mora: 2 * 5250 + 6 * 1750,
+                materials: BTreeMap::from([(104301, 18)]),
+            }
+        );
+        assert_eq!(
+            data.get_base_materials(104302, 4).unwrap(),
+            Cost {
+                mora: 4 * 1750,
+                materials: BTreeMap::from([(104301, 12)]),
+            }
+        );
+        assert_eq!(
+            data.get_base_materials(202, 100).unwrap(),
+            Cost {
+                mora: 0,
+                materials: BTreeMap::from([(202, 100)]),
+            }
+        );
+        assert!(data.get_base_materials(104303, u32::MAX).is_err());
     }
 
     #[tokio::test]
@@ -761,6 +3617,72 @@ mod tests {
         assert_eq!(data.get_material(100002).unwrap(), &"Sunsettia".to_string());
     }
 
+    #[tokio::test]
+    async fn material_info_returns_metadata() {
+        let source = TestDataSource;
+        let mut data = AnimeGameData::new();
+        data.update_impl(&source).await.unwrap();
+
+        assert_eq!(
+            data.get_material_info(104013).unwrap(),
+            &Material {
+                name: "Mystic Enhancement Ore".into(),
+                description: "Weapon Enhancement Material. Contains a pure, dense mass of \
+                              mystic power."
+                    .into(),
+                material_type: Some(MaterialType::WeaponExpStone),
+                item_type: "ITEM_MATERIAL".into(),
+                type_description: "Weapon Enhancement Material".into(),
+                rarity: 3,
+                icon: "UI_ItemIcon_104013".into(),
+                stack_limit: 9999,
+                item_use: vec![ItemUse {
+                    op: "ITEM_USE_ADD_WEAPON_EXP".into(),
+                    params: vec!["10000".into()],
+                }],
+            }
+        );
+
+        // Mora is a virtual item without a material type.
+        let mora = data.get_material_info(202).unwrap();
+        assert_eq!(mora.material_type, None);
+        assert!(mora.item_use.is_empty());
+
+        // Types sort in the game's order, so materials can be grouped by them.
+        let mut groups: BTreeMap<&MaterialType, Vec<u32>> = BTreeMap::new();
+        for (id, material) in &data.db().unwrap().material_map {
+            if let Some(material_type) = &material.material_type {
+                groups.entry(material_type).or_default().push(*id);
+            }
+        }
+        assert_eq!(
+            groups.keys().copied().cloned().collect::<Vec<_>>(),
+            vec![
+                MaterialType::Food,
+                MaterialType::Consume,
+                MaterialType::ExpFruit,
+                MaterialType::WeaponExpStone,
+                MaterialType::AvatarMaterial,
+                MaterialType::NoticeAddHp,
+            ]
+        );
+        assert_eq!(
+            groups[&MaterialType::ExpFruit],
+            vec![104001, 104002, 104003]
+        );
+        assert!(MaterialType::Bgm < MaterialType::Other("MATERIAL_NEW_THING".into()));
+
+        let book = data.get_material_info(104301).unwrap();
+        assert_eq!(book.name, "Teachings of Transience");
+        assert_eq!(book.icon, "UI_ItemIcon_104301");
+        assert_eq!(book.type_description, "Character Talent Material");
+
+        assert_eq!(
+            MaterialType::from_game_data_name("MATERIAL_NEW_THING"),
+            MaterialType::Other("MATERIAL_NEW_THING".into())
+        );
+    }
+
     #[tokio::test]
     async fn affix_map_returns_correct_affix() {
         let source = TestDataSource;
@@ -796,6 +3718,7 @@ mod tests {
             data.get_artifact(31534).unwrap(),
             &Artifact {
                 set: "Marechaussee Hunter".into(),
+                set_id: 15031,
                 slot: ArtifactSlot::Circlet,
                 rarity: 5
             }
@@ -845,9 +3768,83 @@ mod tests {
             data.get_weapon(11505).unwrap(),
             &Weapon {
                 name: "Primordial Jade Cutter".into(),
-                rarity: 5
+                rarity: 5,
+                weapon_type: Some(WeaponType::Sword),
+                base_attack: 44.3358,
+                attack_curve: "GROW_CURVE_ATTACK_304".into(),
+                secondary_property: Some(Property::CritRate),
+                secondary_base_value: 0.096,
+                secondary_curve: "GROW_CURVE_CRITICAL_301".into(),
+                promote_id: 11505,
+                base_exp: 300000,
+                refinement_mora: vec![2000, 4000, 8000, 16000],
+                refinement_material: None,
+            }
+        );
+
+        // Weapons without a type or base attack are still indexed.
+        let weapon = data.get_weapon(19901).unwrap();
+        assert_eq!(weapon.name, "Test Blade");
+        assert_eq!(weapon.weapon_type, None);
+        assert_eq!(weapon.base_attack, 0.0);
+        assert!(data.get_weapon_stats(19901, 1, 0).is_err());
+    }
+
+    #[tokio::test]
+    async fn weapon_passive_map_returns_refinements() {
+        let source = TestDataSource;
+        let mut data = AnimeGameData::new();
+        data.update_impl(&source).await.unwrap();
+
+        let passive = data.get_weapon_passive(11505).unwrap();
+        assert_eq!(passive.name, "Protector's Virtue");
+        assert_eq!(passive.refinements.len(), 5);
+        assert_eq!(
+            passive.refinements[0],
+            WeaponRefinement {
+                description: "HP increased by 20%. Additionally, provides an ATK Bonus based on \
+                              1.2% of the wielder's Max HP."
+                    .into(),
+                stats: vec![Affix {
+                    property: Property::HpPercent,
+                    value: 20.0
+                }],
+                params: vec![0.2, 0.012],
+            }
+        );
+        assert_eq!(passive.refinements[4].params, vec![0.4, 0.024]);
+    }
+
+    #[tokio::test]
+    async fn weapon_stats_scale_with_level_and_ascension() {
+        let source = TestDataSource;
+        let mut data = AnimeGameData::new();
+        data.update_impl(&source).await.unwrap();
+
+        assert_eq!(
+            data.get_weapon_stats(11505, 1, 0).unwrap(),
+            WeaponStats {
+                attack: 44.3358,
+                secondary: Some(Affix {
+                    property: Property::CritRate,
+                    value: 0.096 * 100.
+                }),
+            }
+        );
+
+        // Secondary stats only scale with level, not ascension.
+        assert_eq!(
+            data.get_weapon_stats(11505, 90, 6).unwrap(),
+            WeaponStats {
+                attack: 44.3358 * 8.013 + 186.7,
+                secondary: Some(Affix {
+                    property: Property::CritRate,
+                    value: 0.096 * 4.594 * 100.
+                }),
             }
         );
+
+        assert!(data.get_weapon_stats(11505, 90, 5).is_err());
     }
 
     #[tokio::test]
@@ -973,12 +3970,22 @@ mod tests {
         // Force an old database version to be cached.
         let mut data = AnimeGameData::new_with_cache(tempfile.path());
         data.update_impl(&source).await.unwrap();
-        data.db.as_mut().unwrap().version = 0;
+        Arc::get_mut(data.db.as_mut().unwrap()).unwrap().version = 0;
         data.try_save_db().unwrap();
         drop(data);
 
         // Re-open with cache and ensure the old data is not loaded.
         let mut data = AnimeGameData::new_with_cache(tempfile.path());
+        assert_eq!(
+            data.load_outcome(),
+            &LoadOutcome::Fallback {
+                reason: Some(LoadError::WrongVersion {
+                    found: 0,
+                    expected: DATABASE_VERSION
+                }),
+                embedded: false,
+            }
+        );
 
         // Affix does not exist before update
         assert!(data.get_affix(501022).is_err());
@@ -1021,4 +4028,252 @@ mod tests {
             }
         );
     }
+
+    #[cfg(feature = "shared")]
+    #[tokio::test]
+    async fn shared_data_swaps_in_updates() {
+        let shared = SharedAnimeGameData::new(AnimeGameData::new());
+        let mut receiver = shared.subscribe();
+        let before = shared.snapshot();
+
+        // An update with new data is swapped in and notifies subscribers...
+        assert!(shared.update_impl(&TestDataSource).await.unwrap());
+        assert!(receiver.has_changed().unwrap());
+        assert_eq!(
+            receiver
+                .borrow_and_update()
+                .get_character(10000061)
+                .unwrap(),
+            &"Kirara".to_string()
+        );
+
+        // ...while snapshots taken earlier are unchanged.
+        assert!(!before.has_data());
+
+        // Updating from the same source leaves the data in place.
+        assert!(!shared.update_impl(&TestDataSource).await.unwrap());
+        assert!(!receiver.has_changed().unwrap());
+
+        let before = shared.snapshot();
+        assert!(shared.update_impl(&TestDataSource2).await.unwrap());
+        assert_eq!(
+            before.get_affix(501022).unwrap(),
+            &Affix {
+                property: Property::Hp,
+                value: 239.0
+            }
+        );
+        assert_eq!(
+            shared.snapshot().get_affix(501022).unwrap(),
+            &Affix {
+                property: Property::Hp,
+                value: 240.0
+            }
+        );
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[tokio::test]
+    async fn sqlite_export_joins_artifacts_to_sets() {
+        let source = TestDataSource;
+        let mut data = AnimeGameData::new();
+        data.update_impl(&source).await.unwrap();
+
+        let mut connection = rusqlite::Connection::open_in_memory().unwrap();
+        let foreign_keys = |connection: &rusqlite::Connection| -> bool {
+            connection
+                .query_row("PRAGMA foreign_keys", [], |row| row.get(0))
+                .unwrap()
+        };
+        // The bundled SQLite enforces foreign keys by default.
+        assert!(foreign_keys(&connection));
+        data.export_sqlite(&mut connection).unwrap();
+
+        let (set, slot, rarity): (String, String, u32) = connection
+            .query_row(
+                "SELECT sets.name, artifacts.slot, artifacts.rarity FROM artifacts
+                 JOIN sets ON sets.id = artifacts.set_id WHERE artifacts.id = ?1",
+                [31534],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .unwrap();
+        assert_eq!(
+            (set.as_str(), slot.as_str(), rarity),
+            ("Marechaussee Hunter", "Circlet", 5)
+        );
+
+        let git_hash: String = connection
+            .query_row(
+                "SELECT value FROM metadata WHERE key = 'git_hash'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(git_hash, "13be4fd7343fe4cee8fa0096fe854b1c5b01b124");
+
+        // Skills with both a type and an element are merged into one row.
+        let (skill_type, element): (String, String) = connection
+            .query_row(
+                "SELECT type, element FROM skills WHERE id = ?1",
+                [10019],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!((skill_type.as_str(), element.as_str()), ("Burst", "Cryo"));
+
+        // Exporting again replaces the existing tables, with foreign keys
+        // still enforced.
+        data.export_sqlite(&mut connection).unwrap();
+        assert!(foreign_keys(&connection));
+        let violations: i64 = connection
+            .query_row("SELECT COUNT(*) FROM pragma_foreign_key_check", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(violations, 0);
+    }
+
+    #[tokio::test]
+    async fn saved_data_is_deterministic() {
+        let source = TestDataSource;
+
+        let mut saves = Vec::new();
+        for _ in 0..2 {
+            let mut data = AnimeGameData::new();
+            data.update_impl(&source).await.unwrap();
+            let mut writer = Vec::new();
+            data.save_to_writer(&mut writer).unwrap();
+            saves.push(String::from_utf8(writer).unwrap());
+        }
+        assert_eq!(saves[0], saves[1]);
+
+        // Entries are sorted by id.
+        let save = &saves[0][saves[0].find("\"set_map\"").unwrap()..];
+        let set_positions: Vec<usize> = ["\"10001\"", "\"15031\"", "\"15048\""]
+            .iter()
+            .map(|id| save.find(id).unwrap())
+            .collect();
+        assert!(set_positions.is_sorted());
+    }
+
+    #[tokio::test]
+    async fn load_outcome_reports_why_cache_was_discarded() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let cache_path = tempdir.path().join("cache.json");
+
+        let data = AnimeGameData::new();
+        assert_eq!(
+            data.load_outcome(),
+            &LoadOutcome::Fallback {
+                reason: None,
+                embedded: false,
+            }
+        );
+
+        let data = AnimeGameData::new_with_cache(&cache_path);
+        assert_eq!(
+            data.load_outcome(),
+            &LoadOutcome::Fallback {
+                reason: Some(LoadError::Missing),
+                embedded: false,
+            }
+        );
+
+        std::fs::write(&cache_path, "{\n  \"version\": ").unwrap();
+        let data = AnimeGameData::new_with_cache(&cache_path);
+        assert!(matches!(
+            data.load_outcome(),
+            LoadOutcome::Fallback {
+                reason: Some(LoadError::ParseError {
+                    line: 2,
+                    column: 13,
+                    ..
+                }),
+                ..
+            }
+        ));
+
+        // Valid JSON with a dangling artifact set fails the integrity check.
+        let mut data = AnimeGameData::new_with_cache(&cache_path);
+        data.update_impl(&TestDataSource).await.unwrap();
+        Arc::get_mut(data.db.as_mut().unwrap())
+            .unwrap()
+            .artifact_map
+            .get_mut(&31534)
+            .unwrap()
+            .set_id = 99999;
+        data.try_save_db().unwrap();
+        let data = AnimeGameData::new_with_cache(&cache_path);
+        assert!(matches!(
+            data.load_outcome(),
+            LoadOutcome::Fallback {
+                reason: Some(LoadError::IntegrityMismatch(_)),
+                ..
+            }
+        ));
+        assert!(!data.has_data());
+
+        let mut data = AnimeGameData::new_with_cache(&cache_path);
+        data.update_impl(&TestDataSource).await.unwrap();
+        assert_eq!(data.load_outcome(), &LoadOutcome::Updated);
+        drop(data);
+        let data = AnimeGameData::new_with_cache(&cache_path);
+        assert_eq!(data.load_outcome(), &LoadOutcome::Loaded);
+        assert!(data.has_data());
+    }
+
+    #[tokio::test]
+    async fn snapshot_loads_saved_data() {
+        let mut data = AnimeGameData::new();
+        data.update_impl(&TestDataSource).await.unwrap();
+        let mut writer = Vec::new();
+        data.save_to_writer(&mut writer).unwrap();
+        let json = String::from_utf8(writer).unwrap();
+
+        let db = Database::load_snapshot(&json).unwrap();
+        assert_eq!(db.git_hash, data.db().unwrap().git_hash);
+        assert_eq!(
+            db.character_map.len(),
+            data.db().unwrap().character_map.len()
+        );
+
+        assert!(Database::load_snapshot("null").is_none());
+        assert!(Database::load_snapshot("{\"version\": 0}").is_none());
+    }
+
+    #[tokio::test]
+    async fn reader_with_old_database_version_is_rejected() {
+        let source = TestDataSource;
+
+        let mut data = AnimeGameData::new();
+        data.update_impl(&source).await.unwrap();
+        Arc::get_mut(data.db.as_mut().unwrap()).unwrap().version = 0;
+        let mut writer = Vec::new();
+        data.save_to_writer(&mut writer).unwrap();
+
+        let e = AnimeGameData::new_from_reader(writer.as_slice()).unwrap_err();
+        assert_eq!(
+            e.downcast_ref::<LoadError>(),
+            Some(&LoadError::WrongVersion {
+                found: 0,
+                expected: DATABASE_VERSION
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn update_downloads_each_file_once() {
+        let source = TestDataSource4::default();
+        let mut data = AnimeGameData::new();
+        data.update_impl(&source).await.unwrap();
+
+        let paths = source.paths.into_inner().unwrap();
+        for path in &paths {
+            assert_eq!(
+                paths.iter().filter(|other| *other == path).count(),
+                1,
+                "{path} was downloaded more than once"
+            );
+        }
+    }
 }
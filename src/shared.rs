@@ -0,0 +1,77 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use tokio::sync::{Mutex, watch};
+use tokio::task::JoinHandle;
+
+use crate::{AnimeGameData, Dimbreath, GameDataSource};
+
+// A cloneable handle for sharing data between tasks.  Readers take cheap
+// snapshots that never change underneath them, while updates index into a
+// private copy and atomically swap it in when done.
+#[derive(Clone, Debug)]
+pub struct SharedAnimeGameData {
+    data: Arc<watch::Sender<AnimeGameData>>,
+    // Serializes updates so concurrent refreshes don't index the same data
+    // twice.
+    update_lock: Arc<Mutex<()>>,
+}
+
+impl SharedAnimeGameData {
+    pub fn new(data: AnimeGameData) -> Self {
+        Self {
+            data: Arc::new(watch::Sender::new(data)),
+            update_lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    pub fn snapshot(&self) -> AnimeGameData {
+        self.data.borrow().clone()
+    }
+
+    // The receiver is notified each time new data is swapped in.
+    pub fn subscribe(&self) -> watch::Receiver<AnimeGameData> {
+        self.data.subscribe()
+    }
+
+    // Returns whether new data was swapped in.
+    pub async fn update(&self) -> Result<bool> {
+        self.update_impl(&Dimbreath::new()?).await
+    }
+
+    pub(crate) async fn update_impl<Source: GameDataSource>(
+        &self,
+        source: &Source,
+    ) -> Result<bool> {
+        let _guard = self.update_lock.lock().await;
+
+        let mut data = self.snapshot();
+        data.update_impl(source).await?;
+
+        // An up to date database is left untouched by `update_impl`.
+        let changed = !matches!(
+            (&self.data.borrow().db, &data.db),
+            (Some(old), Some(new)) if Arc::ptr_eq(old, new)
+        );
+        if changed {
+            self.data.send_replace(data);
+        }
+        Ok(changed)
+    }
+
+    // Periodically updates in the background.  The task holds its own handle,
+    // so it runs until the returned `JoinHandle` is aborted.
+    pub fn spawn_refresh(&self, period: Duration) -> JoinHandle<()> {
+        let shared = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            loop {
+                interval.tick().await;
+                if let Err(e) = shared.update().await {
+                    tracing::warn!("Background update failed: {e:#}");
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,281 @@
+use anyhow::{Result, anyhow};
+use rusqlite::{Connection, params};
+use serde::Serialize;
+
+use crate::AnimeGameData;
+
+// Existing tables are replaced so exports can be re-run into the same file.
+// Artifacts and set bonuses reference sets, so they are dropped first.
+const SCHEMA: &str = "
+DROP TABLE IF EXISTS artifacts;
+DROP TABLE IF EXISTS set_bonuses;
+DROP TABLE IF EXISTS affixes;
+DROP TABLE IF EXISTS characters;
+DROP TABLE IF EXISTS const_values;
+DROP TABLE IF EXISTS materials;
+DROP TABLE IF EXISTS metadata;
+DROP TABLE IF EXISTS properties;
+DROP TABLE IF EXISTS sets;
+DROP TABLE IF EXISTS skills;
+DROP TABLE IF EXISTS weapons;
+
+CREATE TABLE metadata (
+    key TEXT PRIMARY KEY,
+    value TEXT NOT NULL
+);
+
+CREATE TABLE affixes (
+    id INTEGER PRIMARY KEY,
+    property TEXT NOT NULL,
+    value REAL NOT NULL
+);
+
+CREATE TABLE sets (
+    id INTEGER PRIMARY KEY,
+    name TEXT NOT NULL
+);
+
+CREATE TABLE set_bonuses (
+    set_id INTEGER NOT NULL REFERENCES sets(id),
+    pieces INTEGER NOT NULL,
+    description TEXT NOT NULL,
+    PRIMARY KEY (set_id, pieces)
+);
+
+CREATE TABLE artifacts (
+    id INTEGER PRIMARY KEY,
+    set_id INTEGER NOT NULL REFERENCES sets(id),
+    slot TEXT NOT NULL,
+    rarity INTEGER NOT NULL
+);
+
+CREATE TABLE characters (
+    id INTEGER PRIMARY KEY,
+    name TEXT NOT NULL,
+    rarity INTEGER,
+    weapon_type TEXT,
+    element TEXT,
+    body_type TEXT,
+    icon_name TEXT NOT NULL,
+    side_icon_name TEXT NOT NULL,
+    initial_weapon INTEGER NOT NULL,
+    skill_depot_id INTEGER NOT NULL,
+    base_hp REAL NOT NULL,
+    base_attack REAL NOT NULL,
+    base_defense REAL NOT NULL
+);
+
+CREATE TABLE const_values (
+    name TEXT PRIMARY KEY,
+    value INTEGER NOT NULL
+);
+
+CREATE TABLE materials (
+    id INTEGER PRIMARY KEY,
+    name TEXT NOT NULL
+);
+
+CREATE TABLE properties (
+    id INTEGER PRIMARY KEY,
+    property TEXT NOT NULL
+);
+
+CREATE TABLE skills (
+    id INTEGER PRIMARY KEY,
+    type TEXT,
+    element TEXT
+);
+
+CREATE TABLE weapons (
+    id INTEGER PRIMARY KEY,
+    name TEXT NOT NULL,
+    rarity INTEGER NOT NULL,
+    weapon_type TEXT,
+    base_attack REAL NOT NULL,
+    secondary_property TEXT
+);
+";
+
+// Enums are stored the same way they are serialized to the JSON cache.
+fn variant_name<T: Serialize>(value: &T) -> Result<String> {
+    match serde_json::to_value(value)? {
+        serde_json::Value::String(name) => Ok(name),
+        value => Err(anyhow!("Unable to name variant {value}")),
+    }
+}
+
+impl AnimeGameData {
+    // Exports the id keyed maps: affixes, artifacts, characters, const values,
+    // materials, properties, sets and their bonuses, skills and weapons.
+    // Curves, ascensions, costs and the other derived data are only available
+    // through the Rust API.
+    //
+    // Foreign keys are enforced whenever the connection has `foreign_keys`
+    // on, which the bundled SQLite does by default.  The setting itself is
+    // left as the caller configured it.
+    // The export runs in a single transaction and leaves existing tables
+    // untouched if it fails.
+    pub fn export_sqlite(&self, connection: &mut Connection) -> Result<()> {
+        let db = self.db()?;
+
+        let tx = connection.transaction()?;
+        tx.execute_batch(SCHEMA)?;
+
+        {
+            let mut stmt = tx.prepare("INSERT INTO metadata (key, value) VALUES (?1, ?2)")?;
+            stmt.execute(params!["git_hash", db.git_hash])?;
+            stmt.execute(params!["version", db.version.to_string()])?;
+        }
+
+        {
+            let mut stmt =
+                tx.prepare("INSERT INTO affixes (id, property, value) VALUES (?1, ?2, ?3)")?;
+            for (id, affix) in &db.affix_map {
+                stmt.execute(params![id, variant_name(&affix.property)?, affix.value])?;
+            }
+        }
+
+        {
+            let mut stmt = tx.prepare("INSERT INTO sets (id, name) VALUES (?1, ?2)")?;
+            for (id, set) in &db.set_map {
+                stmt.execute(params![id, set.name])?;
+            }
+        }
+
+        {
+            let mut stmt = tx.prepare(
+                "INSERT INTO set_bonuses (set_id, pieces, description) VALUES (?1, ?2, ?3)",
+            )?;
+            for (id, set) in &db.set_map {
+                for bonus in &set.bonuses {
+                    stmt.execute(params![id, bonus.pieces, bonus.description])?;
+                }
+            }
+        }
+
+        {
+            let mut stmt = tx.prepare(
+                "INSERT INTO artifacts (id, set_id, slot, rarity) VALUES (?1, ?2, ?3, ?4)",
+            )?;
+            for (id, artifact) in &db.artifact_map {
+                stmt.execute(params![
+                    id,
+                    artifact.set_id,
+                    variant_name(&artifact.slot)?,
+                    artifact.rarity
+                ])?;
+            }
+        }
+
+        {
+            let mut stmt = tx.prepare(
+                "INSERT INTO characters (
+                     id, name, rarity, weapon_type, element, body_type, icon_name,
+                     side_icon_name, initial_weapon, skill_depot_id, base_hp,
+                     base_attack, base_defense
+                 ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+            )?;
+            for (id, character) in &db.character_map {
+                let weapon_type = character
+                    .weapon_type
+                    .as_ref()
+                    .map(variant_name)
+                    .transpose()?;
+                let element = character.element.as_ref().map(variant_name).transpose()?;
+                let body_type = character.body_type.as_ref().map(variant_name).transpose()?;
+                stmt.execute(params![
+                    id,
+                    character.name,
+                    character.rarity,
+                    weapon_type,
+                    element,
+                    body_type,
+                    character.icon_name,
+                    character.side_icon_name,
+                    character.initial_weapon,
+                    character.skill_depot_id,
+                    character.base_hp,
+                    character.base_attack,
+                    character.base_defense,
+                ])?;
+            }
+        }
+
+        {
+            let mut stmt = tx.prepare("INSERT INTO const_values (name, value) VALUES (?1, ?2)")?;
+            let const_values = [
+                (
+                    "CONST_VALUE_TPS_AVATAR_CONFIG_ID_FEMALE",
+                    db.tps_avatar_id_female,
+                ),
+                (
+                    "CONST_VALUE_TPS_AVATAR_CONFIG_ID_MALE",
+                    db.tps_avatar_id_male,
+                ),
+            ];
+            for (name, value) in const_values {
+                if let Some(value) = value {
+                    stmt.execute(params![name, value])?;
+                }
+            }
+        }
+
+        {
+            let mut stmt = tx.prepare("INSERT INTO materials (id, name) VALUES (?1, ?2)")?;
+            for (id, material) in &db.material_map {
+                stmt.execute(params![id, material.name])?;
+            }
+        }
+
+        {
+            let mut stmt = tx.prepare("INSERT INTO properties (id, property) VALUES (?1, ?2)")?;
+            for (id, property) in &db.property_map {
+                stmt.execute(params![id, variant_name(property)?])?;
+            }
+        }
+
+        {
+            // Skill types and elements are indexed separately and not every
+            // skill has both.
+            let mut stmt = tx.prepare(
+                "INSERT INTO skills (id, type, element) VALUES (?1, ?2, ?3)
+                 ON CONFLICT (id) DO UPDATE SET
+                     type = coalesce(excluded.type, type),
+                     element = coalesce(excluded.element, element)",
+            )?;
+            for (id, skill_type) in &db.skill_type_map {
+                stmt.execute(params![id, variant_name(skill_type)?, None::<String>])?;
+            }
+            for (id, element) in &db.skill_element_map {
+                stmt.execute(params![id, None::<String>, variant_name(element)?])?;
+            }
+        }
+
+        {
+            let mut stmt = tx.prepare(
+                "INSERT INTO weapons (
+                     id, name, rarity, weapon_type, base_attack, secondary_property
+                 ) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            )?;
+            for (id, weapon) in &db.weapon_map {
+                let weapon_type = weapon.weapon_type.as_ref().map(variant_name).transpose()?;
+                let secondary_property = weapon
+                    .secondary_property
+                    .as_ref()
+                    .map(variant_name)
+                    .transpose()?;
+                stmt.execute(params![
+                    id,
+                    weapon.name,
+                    weapon.rarity,
+                    weapon_type,
+                    weapon.base_attack,
+                    secondary_property,
+                ])?;
+            }
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+}
@@ -1,9 +1,10 @@
+use std::collections::BTreeMap;
 use std::str::FromStr;
 
 use anyhow::{Error, anyhow};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Deserialize, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct Affix {
     pub property: Property,
     pub value: f64,
@@ -12,11 +13,20 @@ pub struct Affix {
 #[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct Artifact {
     pub set: String,
+    pub set_id: u32,
     pub slot: ArtifactSlot,
     pub rarity: u32,
 }
 
-#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct ArtifactSet {
+    pub name: String,
+    // Ordered by the number of pieces needed.
+    pub bonuses: Vec<SetBonus>,
+    pub artifacts: Vec<u32>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
 pub enum ArtifactSlot {
     Flower,
     Plume,
@@ -48,6 +58,137 @@ impl ArtifactSlot {
     }
 }
 
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum BodyType {
+    Boy,
+    Girl,
+    Lady,
+    Male,
+    Loli,
+}
+
+impl FromStr for BodyType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "BODY_BOY" => Ok(Self::Boy),
+            "BODY_GIRL" => Ok(Self::Girl),
+            "BODY_LADY" => Ok(Self::Lady),
+            "BODY_MALE" => Ok(Self::Male),
+            "BODY_LOLI" => Ok(Self::Loli),
+            _ => Err(anyhow!("unknown body type {s}")),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Birthday {
+    pub month: u32,
+    pub day: u32,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct Character {
+    pub name: String,
+    // Test avatars and the like have no known rarity, weapon or body type.
+    pub rarity: Option<u32>,
+    pub weapon_type: Option<WeaponType>,
+    // Characters whose element can change, like the Traveler, have none.
+    pub element: Option<Element>,
+    pub body_type: Option<BodyType>,
+    pub icon_name: String,
+    pub side_icon_name: String,
+    pub initial_weapon: u32,
+    pub skill_depot_id: u32,
+    pub base_hp: f64,
+    pub base_attack: f64,
+    pub base_defense: f64,
+    pub promote_id: u32,
+    pub hp_curve: String,
+    pub attack_curve: String,
+    pub defense_curve: String,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct CharacterProfile {
+    pub title: String,
+    pub description: String,
+    pub affiliation: String,
+    // Text is in the loaded language, and left empty where the game has none.
+    pub vision: String,
+    pub constellation: String,
+    // The Traveler's birthday is chosen by the player.
+    pub birthday: Option<Birthday>,
+    pub voice_actors: VoiceActors,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct CharacterStats {
+    pub hp: f64,
+    pub attack: f64,
+    pub defense: f64,
+    // The stat granted by ascending, e.g. Crit Rate.
+    pub bonus: Option<Affix>,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct Constellation {
+    pub name: String,
+    pub description: String,
+    pub icon: String,
+    pub skill_level_bonus: Option<SkillLevelBonus>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Cost {
+    pub mora: u32,
+    // Material id to count.
+    pub materials: BTreeMap<u32, u32>,
+}
+
+impl Cost {
+    pub fn add(&mut self, other: &Cost) {
+        self.mora += other.mora;
+        for (id, count) in &other.materials {
+            *self.materials.entry(*id).or_default() += count;
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct Domain {
+    pub name: String,
+    // Shared by each difficulty of the domain, empty if it has none.
+    pub entrance: String,
+    pub domain_type: DomainType,
+    pub recommended_level: u32,
+    pub min_adventure_rank: u32,
+    pub artifact_sets: Vec<u32>,
+    pub materials: Vec<u32>,
+    pub weekdays: Vec<Weekday>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum DomainType {
+    Artifact,
+    TalentBook,
+    WeaponMaterial,
+}
+
+impl FromStr for DomainType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "DUNGEON_SUB_RELIQUARY" => Ok(Self::Artifact),
+            "DUNGEON_SUB_TALENT" => Ok(Self::TalentBook),
+            "DUNGEON_SUB_WEAPON" => Ok(Self::WeaponMaterial),
+            _ => Err(anyhow!("unknown domain type {s}")),
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub enum Element {
     Anemo,
@@ -90,7 +231,145 @@ impl FromStr for Element {
     }
 }
 
-#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct ItemUse {
+    pub op: String,
+    pub params: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct LevelUpCost {
+    pub exp: u32,
+    // The materials fed to reach `exp`, which may overshoot it slightly.
+    pub cost: Cost,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct MainStatOption {
+    pub property: Property,
+    // Relative drop weight, omitted by some depots.
+    pub weight: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct Material {
+    pub name: String,
+    pub description: String,
+    // Items without a material type, like Mora, have none.
+    pub material_type: Option<MaterialType>,
+    pub item_type: String,
+    pub type_description: String,
+    pub rarity: u32,
+    pub icon: String,
+    pub stack_limit: u32,
+    pub item_use: Vec<ItemUse>,
+}
+
+// Variants follow the numbering of the game's own material type enum, so
+// sorting by type matches the game.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+pub enum MaterialType {
+    Food,
+    Quest,
+    Exchange,
+    Consume,
+    ExpFruit,
+    Cricket,
+    ElemCrystal,
+    WeaponExpStone,
+    Chest,
+    ReliquaryMaterial,
+    AvatarMaterial,
+    NoticeAddHp,
+    SeaLamp,
+    SelectableChest,
+    Flycloak,
+    Namecard,
+    Talent,
+    Widget,
+    ConsumeBatchUse,
+    Wood,
+    FurnitureFormula,
+    Costume,
+    FishBait,
+    FishRod,
+    Bgm,
+    // New or rarely used types are kept by their game data name, after the
+    // known ones.
+    Other(String),
+}
+
+impl MaterialType {
+    pub fn from_game_data_name(name: &str) -> Self {
+        match name {
+            "MATERIAL_AVATAR_MATERIAL" => Self::AvatarMaterial,
+            "MATERIAL_BGM" => Self::Bgm,
+            "MATERIAL_CHEST" => Self::Chest,
+            "MATERIAL_CONSUME" => Self::Consume,
+            "MATERIAL_CONSUME_BATCH_USE" => Self::ConsumeBatchUse,
+            "MATERIAL_COSTUME" => Self::Costume,
+            "MATERIAL_CRICKET" => Self::Cricket,
+            "MATERIAL_ELEM_CRYSTAL" => Self::ElemCrystal,
+            "MATERIAL_EXCHANGE" => Self::Exchange,
+            "MATERIAL_EXP_FRUIT" => Self::ExpFruit,
+            "MATERIAL_FISH_BAIT" => Self::FishBait,
+            "MATERIAL_FISH_ROD" => Self::FishRod,
+            "MATERIAL_FLYCLOAK" => Self::Flycloak,
+            "MATERIAL_FOOD" => Self::Food,
+            "MATERIAL_FURNITURE_FORMULA" => Self::FurnitureFormula,
+            "MATERIAL_NAMECARD" => Self::Namecard,
+            "MATERIAL_NOTICE_ADD_HP" => Self::NoticeAddHp,
+            "MATERIAL_QUEST" => Self::Quest,
+            "MATERIAL_RELIQUARY_MATERIAL" => Self::ReliquaryMaterial,
+            "MATERIAL_SEA_LAMP" => Self::SeaLamp,
+            "MATERIAL_SELECTABLE_CHEST" => Self::SelectableChest,
+            "MATERIAL_TALENT" => Self::Talent,
+            "MATERIAL_WEAPON_EXP_STONE" => Self::WeaponExpStone,
+            "MATERIAL_WIDGET" => Self::Widget,
+            "MATERIAL_WOOD" => Self::Wood,
+            _ => Self::Other(name.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct Monster {
+    pub name: String,
+    pub icon: String,
+    pub base_hp: f64,
+    pub base_attack: f64,
+    pub base_defense: f64,
+    pub hp_curve: String,
+    pub attack_curve: String,
+    pub defense_curve: String,
+    pub resistances: Resistances,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct MonsterStats {
+    pub hp: f64,
+    pub attack: f64,
+    pub defense: f64,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct ParamLabel {
+    pub name: String,
+    // Refers to params by 1-based index, e.g. "{param1:F1P}".
+    pub format: String,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct Passive {
+    pub name: String,
+    pub description: String,
+    pub icon: String,
+    // Passives available from the start unlock at ascension 0.
+    pub unlock_ascension: u32,
+    pub stats: Vec<Affix>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
 pub enum Property {
     Hp,
     HpPercent,
@@ -189,6 +468,68 @@ impl FromStr for Property {
     }
 }
 
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct Recipe {
+    pub inputs: Vec<RecipeItem>,
+    // Cooking yields one of its outputs depending on quality, other recipes
+    // yield all of them.
+    pub outputs: Vec<RecipeItem>,
+    pub mora: u32,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct RecipeItem {
+    pub id: u32,
+    pub name: String,
+    pub count: u32,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+pub enum RecipeKind {
+    // Alchemy that turns lower tier materials into higher ones.
+    Crafting,
+    // Alchemy that trades a material for another of the same tier.
+    Conversion,
+    Forging,
+    Cooking,
+}
+
+// Percentages, like affixes.  Immune enemies have very large values.
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct Resistances {
+    pub anemo: f64,
+    pub geo: f64,
+    pub electro: f64,
+    pub hydro: f64,
+    pub pyro: f64,
+    pub cryo: f64,
+    pub dendro: f64,
+    pub physical: f64,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct SetBonus {
+    pub pieces: u32,
+    pub description: String,
+    pub stats: Vec<Affix>,
+    // Conditional effects such as the 4-piece bonus only appear here, as
+    // fractions filled into the description's placeholders.
+    pub params: Vec<f64>,
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SkillLevelBonus {
+    pub skill_id: u32,
+    pub levels: u32,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct SkillScaling {
+    pub labels: Vec<ParamLabel>,
+    // Params for each skill level, starting at level 1.
+    pub levels: Vec<Vec<f64>>,
+}
+
 #[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub enum SkillType {
     Auto,
@@ -196,8 +537,107 @@ pub enum SkillType {
     Burst,
 }
 
-#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+// Both body types share a name and pick their element by switching between
+// skill depots.
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct Traveler {
+    pub name: String,
+    pub male_id: Option<u32>,
+    pub female_id: Option<u32>,
+    pub variants: Vec<TravelerVariant>,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct TravelerVariant {
+    pub character_id: u32,
+    pub depot_id: u32,
+    // The variant without a burst has no element.
+    pub element: Option<Element>,
+    // Auto, Skill and Burst, skipping missing ones.
+    pub skills: Vec<u32>,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct VoiceActors {
+    pub chinese: String,
+    pub japanese: String,
+    pub english: String,
+    pub korean: String,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
 pub struct Weapon {
     pub name: String,
     pub rarity: u32,
+    // Test weapons and the like have no known type or base attack.
+    pub weapon_type: Option<WeaponType>,
+    pub base_attack: f64,
+    pub attack_curve: String,
+    // Low rarity weapons have no secondary stat.
+    pub secondary_property: Option<Property>,
+    pub secondary_base_value: f64,
+    pub secondary_curve: String,
+    pub promote_id: u32,
+    // EXP granted when used to enhance another weapon.
+    pub base_exp: u32,
+    // Mora for each refinement, starting with R1 to R2.
+    pub refinement_mora: Vec<u32>,
+    // Weapons without a refinement material are refined with a copy.
+    pub refinement_material: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct WeaponPassive {
+    pub name: String,
+    // One entry per refinement rank, starting with R1.
+    pub refinements: Vec<WeaponRefinement>,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct WeaponRefinement {
+    pub description: String,
+    pub stats: Vec<Affix>,
+    // Raw values referenced by the description, e.g. 0.2 for 20%.
+    pub params: Vec<f64>,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct WeaponStats {
+    pub attack: f64,
+    pub secondary: Option<Affix>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum WeaponType {
+    Sword,
+    Claymore,
+    Polearm,
+    Bow,
+    Catalyst,
+}
+
+impl FromStr for WeaponType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "WEAPON_SWORD_ONE_HAND" => Ok(Self::Sword),
+            "WEAPON_CLAYMORE" => Ok(Self::Claymore),
+            "WEAPON_POLE" => Ok(Self::Polearm),
+            "WEAPON_BOW" => Ok(Self::Bow),
+            "WEAPON_CATALYST" => Ok(Self::Catalyst),
+            _ => Err(anyhow!("unknown weapon type {s}")),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
 }